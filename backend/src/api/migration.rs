@@ -18,7 +18,7 @@ pub async fn start_migration(
         .map_err(|_| StatusCode::BAD_REQUEST)?;
     
     let job_id = services.migration_manager
-        .start_migration(proposal_id, request.account_addresses)
+        .start_migration(proposal_id, request.proposal_address, request.account_addresses)
         .await
         .map_err(|e| {
             tracing::error!("Failed to start migration: {}", e);
@@ -31,23 +31,47 @@ pub async fn start_migration(
     })))
 }
 
-/// Get migration progress
+/// Get migration progress, read from the on-chain tracker when the job has one
 pub async fn get_progress(
     State(services): State<Arc<Services>>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<MigrationProgress>, StatusCode> {
-    let (total, completed) = services.migration_manager
-        .get_progress(id)
+    let proposal_address = services.migration_manager
+        .get_proposal_address(id)
         .await
         .map_err(|_| StatusCode::NOT_FOUND)?;
     
+    let (total, completed, finished) = match proposal_address {
+        Some(proposal) => {
+            let tracker = services.anchor_client
+                .fetch_migration_tracker(&proposal)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to read migration tracker: {}", e);
+                    StatusCode::BAD_GATEWAY
+                })?;
+            (
+                tracker.total_accounts as i64,
+                tracker.migrated_accounts as i64,
+                tracker.completed_at.is_some(),
+            )
+        }
+        None => {
+            let (total, completed) = services.migration_manager
+                .get_progress(id)
+                .await
+                .map_err(|_| StatusCode::NOT_FOUND)?;
+            (total, completed, completed >= total)
+        }
+    };
+    
     let percentage = if total > 0 {
         (completed as f64 / total as f64) * 100.0
     } else {
         0.0
     };
     
-    let status = if completed >= total {
+    let status = if finished {
         "completed"
     } else {
         "in_progress"
@@ -17,7 +17,7 @@ pub async fn list_proposals(
         Proposal,
         r#"
         SELECT id, proposer, program, new_buffer, description, status,
-               approval_count, proposed_at, timelock_until, executed_at
+               approval_count, timelock_seconds, proposed_at, timelock_until, executed_at
         FROM upgrade_proposals
         ORDER BY proposed_at DESC
         "#
@@ -41,7 +41,7 @@ pub async fn get_proposal(
         Proposal,
         r#"
         SELECT id, proposer, program, new_buffer, description, status,
-               approval_count, proposed_at, timelock_until, executed_at
+               approval_count, timelock_seconds, proposed_at, timelock_until, executed_at
         FROM upgrade_proposals
         WHERE id = $1
         "#,
@@ -61,6 +61,23 @@ pub async fn propose_upgrade(
 ) -> Result<Json<Value>, StatusCode> {
     let proposal_id = Uuid::new_v4();
     
+    let multisig = services.anchor_client
+        .fetch_multisig_config()
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to read multisig config: {}", e);
+            StatusCode::BAD_GATEWAY
+        })?;
+    
+    // The program rejects timelocks shorter than the multisig default or minimum
+    let timelock_seconds = match request.timelock_seconds {
+        Some(seconds) if seconds < multisig.default_timelock || seconds < multisig.min_timelock => {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        Some(seconds) => seconds,
+        None => multisig.default_timelock,
+    };
+    
     // Call on-chain program
     // let tx_sig = services.anchor_client
     //     .propose_upgrade(...)
@@ -71,14 +88,15 @@ pub async fn propose_upgrade(
     sqlx::query!(
         r#"
         INSERT INTO upgrade_proposals
-        (id, proposer, program, new_buffer, description, status, approval_count)
-        VALUES ($1, $2, $3, $4, $5, 'Proposed', 0)
+        (id, proposer, program, new_buffer, description, status, approval_count, timelock_seconds)
+        VALUES ($1, $2, $3, $4, $5, 'Proposed', 0, $6)
         "#,
         proposal_id,
         "system", // Would be actual proposer
         "program_id",
         request.new_program_buffer,
-        request.description
+        request.description,
+        timelock_seconds
     )
     .execute(&services.db_pool)
     .await
@@ -116,9 +134,14 @@ pub async fn approve_upgrade(
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     
     if threshold_met {
-        // Activate timelock
+        // Activate timelock using the period recorded at proposal time
+        let timelock_seconds = services.timelock_manager
+            .get_timelock_seconds(id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        
         services.timelock_manager
-            .set_timelock(id, 48)
+            .set_timelock(id, timelock_seconds)
             .await
             .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     }
@@ -130,6 +153,41 @@ pub async fn approve_upgrade(
     })))
 }
 
+/// Revoke a previous approval
+pub async fn revoke_approval(
+    State(services): State<Arc<Services>>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<RevokeRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    // Call on-chain program
+    // Load approver keypair
+    // Call revoke_approval instruction
+    
+    services.multisig_coordinator
+        .record_revocation(id, "approver_pubkey".to_string())
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    
+    let threshold_met = services.multisig_coordinator
+        .check_threshold(id, 3)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    
+    if !threshold_met {
+        // Mirror the on-chain reset of the timelock countdown
+        services.timelock_manager
+            .clear_timelock(id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+    
+    Ok(Json(json!({
+        "proposal_id": id,
+        "status": "revoked",
+        "threshold_met": threshold_met
+    })))
+}
+
 /// Execute upgrade after timelock
 pub async fn execute_upgrade(
     State(services): State<Arc<Services>>,
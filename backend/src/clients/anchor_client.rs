@@ -6,9 +6,11 @@ use anchor_client::{
         signer::Signer,
     },
 };
+use anchor_lang::AnchorDeserialize;
 use anyhow::Result;
 use std::rc::Rc;
 use std::str::FromStr;
+use crate::models::{OnChainMigrationTracker, OnChainMultisigConfig};
 
 pub struct AnchorClient {
     pub program: Program<Rc<Keypair>>,
@@ -44,6 +46,15 @@ impl AnchorClient {
         Ok("tx_signature".to_string())
     }
     
+    pub async fn revoke_approval(
+        &self,
+        proposal_id: Pubkey,
+        approver: &Keypair,
+    ) -> Result<String> {
+        // Implementation would call the on-chain program
+        Ok("tx_signature".to_string())
+    }
+    
     pub async fn execute_upgrade(
         &self,
         proposal_id: Pubkey,
@@ -70,4 +81,36 @@ impl AnchorClient {
         // Implementation would call the on-chain program
         Ok("tx_signature".to_string())
     }
+    
+    /// Address of the multisig configuration
+    pub fn multisig_config_address(&self) -> Pubkey {
+        Pubkey::find_program_address(&[b"multisig"], &self.program.id()).0
+    }
+    
+    /// Read the multisig configuration, the authority on timelock bounds
+    pub async fn fetch_multisig_config(&self) -> Result<OnChainMultisigConfig> {
+        let data = self.program.rpc().get_account_data(&self.multisig_config_address())?;
+        
+        // Skip the 8-byte account discriminator; fields after the timelocks are not read
+        let config = OnChainMultisigConfig::deserialize(&mut &data[8..])?;
+        Ok(config)
+    }
+    
+    /// Address of the migration tracker for an executed upgrade proposal
+    pub fn migration_tracker_address(&self, proposal: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"migration_tracker", proposal.as_ref()],
+            &self.program.id(),
+        ).0
+    }
+    
+    /// Read migration progress from the on-chain tracker
+    pub async fn fetch_migration_tracker(&self, proposal: &Pubkey) -> Result<OnChainMigrationTracker> {
+        let address = self.migration_tracker_address(proposal);
+        let data = self.program.rpc().get_account_data(&address)?;
+        
+        // Skip the 8-byte account discriminator
+        let tracker = OnChainMigrationTracker::deserialize(&mut &data[8..])?;
+        Ok(tracker)
+    }
 }
@@ -7,6 +7,7 @@ CREATE TABLE IF NOT EXISTS upgrade_proposals (
     description TEXT NOT NULL,
     status TEXT NOT NULL,
     approval_count INTEGER NOT NULL DEFAULT 0,
+    timelock_seconds BIGINT NOT NULL,
     proposed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     timelock_until TIMESTAMPTZ,
     executed_at TIMESTAMPTZ,
@@ -27,6 +28,7 @@ CREATE TABLE IF NOT EXISTS approval_history (
 CREATE TABLE IF NOT EXISTS migration_jobs (
     id UUID PRIMARY KEY,
     proposal_id UUID REFERENCES upgrade_proposals(id) ON DELETE SET NULL,
+    proposal_address TEXT,
     total_accounts BIGINT NOT NULL,
     migrated_accounts BIGINT NOT NULL DEFAULT 0,
     started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
@@ -58,6 +60,13 @@ CREATE TABLE IF NOT EXISTS account_migrations (
     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
 );
 
+-- Columns added after a table was first created, for databases that already have it.
+-- CREATE TABLE IF NOT EXISTS leaves existing tables untouched.
+-- Proposals stored before timelock_seconds existed took the default two-day timelock.
+ALTER TABLE upgrade_proposals ADD COLUMN IF NOT EXISTS timelock_seconds BIGINT NOT NULL DEFAULT 172800;
+-- Jobs started before proposal_address existed have no on-chain tracker to read.
+ALTER TABLE migration_jobs ADD COLUMN IF NOT EXISTS proposal_address TEXT;
+
 -- Indexes
 CREATE INDEX IF NOT EXISTS idx_proposals_status ON upgrade_proposals(status);
 CREATE INDEX IF NOT EXISTS idx_proposals_proposed_at ON upgrade_proposals(proposed_at DESC);
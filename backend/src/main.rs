@@ -45,6 +45,7 @@ async fn main() -> anyhow::Result<()> {
         .route("/proposals/:id", get(api::upgrade::get_proposal))
         .route("/proposals/propose", post(api::upgrade::propose_upgrade))
         .route("/proposals/:id/approve", post(api::upgrade::approve_upgrade))
+        .route("/proposals/:id/revoke", post(api::upgrade::revoke_approval))
         .route("/proposals/:id/execute", post(api::upgrade::execute_upgrade))
         .route("/proposals/:id/cancel", post(api::upgrade::cancel_upgrade))
         .route("/migration/start", post(api::migration::start_migration))
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
+use anchor_lang::AnchorDeserialize;
+use solana_sdk::pubkey::Pubkey;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MigrationJob {
@@ -15,9 +17,22 @@ pub struct MigrationJob {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StartMigrationRequest {
     pub proposal_id: String,
+    /// On-chain address of the executed upgrade proposal
+    pub proposal_address: String,
     pub account_addresses: Vec<String>,
 }
 
+/// On-chain `MigrationTracker` account, the authoritative migration progress
+#[derive(Debug, Clone, AnchorDeserialize)]
+pub struct OnChainMigrationTracker {
+    pub proposal_id: Pubkey,
+    pub total_accounts: u64,
+    pub migrated_accounts: u64,
+    pub started_at: i64,
+    pub completed_at: Option<i64>,
+    pub bump: u8,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MigrationProgress {
     pub job_id: Uuid,
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
+use anchor_lang::AnchorDeserialize;
+use solana_sdk::pubkey::Pubkey;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Proposal {
@@ -11,11 +13,34 @@ pub struct Proposal {
     pub description: String,
     pub status: String,
     pub approval_count: i32,
+    pub timelock_seconds: i64,
     pub proposed_at: DateTime<Utc>,
     pub timelock_until: Option<DateTime<Utc>>,
     pub executed_at: Option<DateTime<Utc>>,
 }
 
+#[derive(Debug, Clone, AnchorDeserialize)]
+pub struct OnChainMember {
+    pub key: Pubkey,
+    pub roles: u8,
+    pub weight: u8,
+}
+
+/// Leading fields of the on-chain `MultisigConfig` account, up to its timelock bounds
+#[derive(Debug, Clone, AnchorDeserialize)]
+pub struct OnChainMultisigConfig {
+    pub authority: Pubkey,
+    pub members: Vec<OnChainMember>,
+    pub threshold: u16,
+    pub cancel_threshold: u16,
+    pub active_cancel_threshold: u16,
+    pub emergency_threshold: u16,
+    pub emergency_timelock: i64,
+    pub paused_scopes: u8,
+    pub min_timelock: i64,
+    pub default_timelock: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Approval {
     pub id: Uuid,
@@ -28,6 +53,8 @@ pub struct Approval {
 pub struct ProposeRequest {
     pub new_program_buffer: String,
     pub description: String,
+    /// Longer timelock than the multisig default, if requested
+    pub timelock_seconds: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +62,11 @@ pub struct ApproveRequest {
     pub approver_keypair_path: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevokeRequest {
+    pub approver_keypair_path: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecuteRequest {
     pub executor_keypair_path: String,
@@ -18,6 +18,7 @@ impl MigrationManager {
     pub async fn start_migration(
         &self,
         proposal_id: Uuid,
+        proposal_address: String,
         account_addresses: Vec<String>,
     ) -> Result<Uuid> {
         let job_id = Uuid::new_v4();
@@ -25,11 +26,12 @@ impl MigrationManager {
         
         sqlx::query!(
             r#"
-            INSERT INTO migration_jobs (id, proposal_id, total_accounts, migrated_accounts)
-            VALUES ($1, $2, $3, 0)
+            INSERT INTO migration_jobs (id, proposal_id, proposal_address, total_accounts, migrated_accounts)
+            VALUES ($1, $2, $3, $4, 0)
             "#,
             job_id,
             proposal_id,
+            proposal_address,
             total
         )
         .execute(&self.db_pool)
@@ -151,6 +153,22 @@ impl MigrationManager {
         Ok(())
     }
     
+    /// On-chain proposal address of a migration job, if it was recorded
+    pub async fn get_proposal_address(&self, job_id: Uuid) -> Result<Option<Pubkey>> {
+        let result = sqlx::query!(
+            r#"
+            SELECT proposal_address
+            FROM migration_jobs
+            WHERE id = $1
+            "#,
+            job_id
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+        
+        Ok(result.proposal_address.map(|address| Pubkey::from_str(&address)).transpose()?)
+    }
+    
     /// Get migration progress
     pub async fn get_progress(&self, job_id: Uuid) -> Result<(i64, i64)> {
         let result = sqlx::query!(
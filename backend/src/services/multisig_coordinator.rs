@@ -66,6 +66,40 @@ impl MultisigCoordinator {
         Ok(())
     }
     
+    /// Remove a previously recorded approval
+    pub async fn record_revocation(
+        &self,
+        proposal_id: Uuid,
+        approver: String,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM approval_history
+            WHERE proposal_id = $1 AND approver = $2
+            "#,
+            proposal_id,
+            approver
+        )
+        .execute(&self.db_pool)
+        .await?;
+        
+        sqlx::query!(
+            r#"
+            UPDATE upgrade_proposals
+            SET approval_count = approval_count - 1,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+            proposal_id
+        )
+        .execute(&self.db_pool)
+        .await?;
+        
+        tracing::info!("Recorded revocation from {} for proposal {}", approver, proposal_id);
+        
+        Ok(())
+    }
+    
     /// Check if threshold is met
     pub async fn check_threshold(
         &self,
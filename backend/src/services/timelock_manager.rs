@@ -48,13 +48,27 @@ impl TimelockManager {
         Ok(())
     }
     
+    /// Get the timelock period recorded for a proposal
+    pub async fn get_timelock_seconds(&self, proposal_id: Uuid) -> Result<i64> {
+        let result = sqlx::query!(
+            r#"
+            SELECT timelock_seconds FROM upgrade_proposals WHERE id = $1
+            "#,
+            proposal_id
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+        
+        Ok(result.timelock_seconds)
+    }
+    
     /// Set timelock period for a proposal
     pub async fn set_timelock(
         &self,
         proposal_id: Uuid,
-        duration_hours: i64,
+        duration_seconds: i64,
     ) -> Result<DateTime<Utc>> {
-        let expiry = Utc::now() + Duration::hours(duration_hours);
+        let expiry = Utc::now() + Duration::seconds(duration_seconds);
         
         sqlx::query!(
             r#"
@@ -75,6 +89,26 @@ impl TimelockManager {
         Ok(expiry)
     }
     
+    /// Clear the timelock after approvals drop below threshold
+    pub async fn clear_timelock(&self, proposal_id: Uuid) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE upgrade_proposals
+            SET timelock_until = NULL,
+                status = 'Proposed',
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+            proposal_id
+        )
+        .execute(&self.db_pool)
+        .await?;
+        
+        tracing::info!("Cleared timelock for proposal {}", proposal_id);
+        
+        Ok(())
+    }
+    
     /// Notify when timelock expires
     async fn notify_timelock_expired(&self, proposal_id: Uuid) -> Result<()> {
         // Send notifications
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_lang::{system_program, Discriminator};
+use program_upgrade_system::SEED_MULTISIG;
+
+declare_id!("Fm7TkCoqUftAQzX7o2ssKhkkjf5DZcR1ff9ivkBjVy2W");
+
+/// Minimal governed program for the migration tests. It owns `FixtureRecord` accounts and
+/// exposes the `apply_migration` hook that `migrate_account` calls.
+#[program]
+pub mod migration_hook_fixture {
+    use super::*;
+    
+    pub fn create_record(ctx: Context<CreateRecord>, value: u64) -> Result<()> {
+        let record = &mut ctx.accounts.record;
+        record.owner = ctx.accounts.owner.key();
+        record.value = value;
+        Ok(())
+    }
+    
+    /// Creates a record directly in the version 2 layout, as the upgraded program would.
+    pub fn create_record_v2(ctx: Context<CreateRecordV2>, value: u64, frozen: bool) -> Result<()> {
+        let mut data = FixtureRecord::DISCRIMINATOR.to_vec();
+        (ctx.accounts.owner.key(), value, frozen).serialize(&mut data)?;
+        
+        system_program::create_account(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.record.to_account_info(),
+                },
+            ),
+            Rent::get()?.minimum_balance(data.len()),
+            data.len() as u64,
+            &crate::ID,
+        )?;
+        ctx.accounts.record.try_borrow_mut_data()?.copy_from_slice(&data);
+        Ok(())
+    }
+    
+    /// Reallocs the account to the migrated length and copies the data in. Only the
+    /// upgrade system's multisig PDA can call it.
+    pub fn apply_migration(ctx: Context<ApplyMigration>, new_data: Vec<u8>) -> Result<()> {
+        let account = ctx.accounts.account.to_account_info();
+        account.realloc(new_data.len(), false)?;
+        account.try_borrow_mut_data()?.copy_from_slice(&new_data);
+        Ok(())
+    }
+}
+
+/// Record layout as first deployed (version 1).
+#[account]
+pub struct FixtureRecord {
+    pub owner: Pubkey,
+    pub value: u64,
+}
+
+impl FixtureRecord {
+    pub const LEN: usize = 8 + 32 + 8;
+}
+
+#[derive(Accounts)]
+pub struct CreateRecord<'info> {
+    #[account(init, payer = owner, space = FixtureRecord::LEN)]
+    pub record: Account<'info, FixtureRecord>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateRecordV2<'info> {
+    #[account(mut)]
+    pub record: Signer<'info>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyMigration<'info> {
+    /// CHECK: Rewritten wholesale with the migrated layout
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [SEED_MULTISIG],
+        bump,
+        seeds::program = program_upgrade_system::ID,
+    )]
+    pub multisig: Signer<'info>,
+}
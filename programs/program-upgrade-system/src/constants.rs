@@ -10,11 +10,89 @@ pub const SEED_PROPOSAL: &[u8] = b"proposal";
 pub const SEED_MIGRATION: &[u8] = b"migration";
 
 #[constant]
-pub const TIMELOCK_PERIOD: i64 = 172800; // 48 hours in seconds
+pub const SEED_MIGRATION_TRACKER: &[u8] = b"migration_tracker";
 
+#[constant]
+pub const SEED_MANAGED_PROGRAM: &[u8] = b"managed_program";
+
+#[constant]
+pub const SEED_HISTORY: &[u8] = b"history";
+
+#[constant]
+pub const SEED_PROPOSAL_SUMMARY: &[u8] = b"proposal_summary";
+
+#[constant]
+pub const SEED_INSTRUCTIONS: &[u8] = b"instructions";
+
+#[constant]
+pub const ROLE_PROPOSER: u8 = 1 << 0;
+
+#[constant]
+pub const ROLE_APPROVER: u8 = 1 << 1;
+
+#[constant]
+pub const ROLE_EXECUTOR: u8 = 1 << 2;
+
+#[constant]
+pub const ROLE_CANCELLER: u8 = 1 << 3;
+
+#[constant]
+pub const ROLE_PAUSER: u8 = 1 << 4;
+
+#[constant]
+pub const ROLE_MIGRATOR: u8 = 1 << 5;
+
+#[constant]
+pub const ROLE_ALL: u8 = ROLE_PROPOSER | ROLE_APPROVER | ROLE_EXECUTOR | ROLE_CANCELLER | ROLE_PAUSER | ROLE_MIGRATOR;
+
+#[constant]
+pub const PAUSE_PROPOSALS: u8 = 1 << 0;
+
+#[constant]
+pub const PAUSE_APPROVALS: u8 = 1 << 1;
+
+#[constant]
+pub const PAUSE_EXECUTION: u8 = 1 << 2;
+
+#[constant]
+pub const PAUSE_MIGRATION: u8 = 1 << 3;
+
+#[constant]
+pub const PAUSE_ALL: u8 = PAUSE_PROPOSALS | PAUSE_APPROVALS | PAUSE_EXECUTION | PAUSE_MIGRATION;
+
+pub const DEFAULT_PAUSE_WINDOW: i64 = 86400; // 24 hours in seconds
+
+pub const DEFAULT_RETENTION_PERIOD: i64 = 2592000; // 30 days in seconds
+
+pub const DEFAULT_CANCEL_THRESHOLD: u16 = 1; // a single canceller before approval
+
+pub const DEFAULT_EMERGENCY_TIMELOCK: i64 = 3600; // 1 hour in seconds
+
+pub const FINALIZE_TIMELOCK: i64 = 604800; // 7 days in seconds
+
+pub const MAX_TIMELOCK: i64 = 31536000; // 365 days in seconds
 
 pub const MAX_DESCRIPTION_LENGTH: usize = 500;
 
+pub const MAX_PAUSE_REASON_LENGTH: usize = 100;
+
+pub const MAX_CANCEL_REASON_LENGTH: usize = 100;
+
 pub const MAX_MULTISIG_MEMBERS: usize = 10;
 
 pub const MAX_APPROVALS: usize = 10;
+
+pub const MAX_GOVERNED_INSTRUCTIONS: usize = 8;
+
+pub const MAX_BUNDLED_UPGRADES: usize = 4;
+
+// Program bytes across an upgrade and its bundle; hashing costs ~1 compute unit per 2 bytes,
+// leaving room under the 1.4M unit transaction limit for the loader CPIs
+pub const MAX_BUNDLE_PROGRAM_BYTES: usize = 2_000_000;
+
+pub const INITIAL_ACCOUNT_VERSION: u8 = 1; // accounts that predate version tracking
+
+// sha256("global:apply_migration")[..8], the hook governed programs expose for migrations
+pub const MIGRATION_HOOK_DISCRIMINATOR: [u8; 8] = [208, 242, 29, 128, 147, 111, 87, 64];
+
+pub const MIGRATION_COMPUTE_RESERVE: u64 = 60_000; // compute units needed to start another account in a batch
@@ -8,7 +8,7 @@ pub enum ErrorCode {
     #[msg("Insufficient approvals - threshold not met")]
     InsufficientApprovals,
     
-    #[msg("Timelock not expired - must wait 48 hours")]
+    #[msg("Timelock not expired")]
     TimelockNotExpired,
     
     #[msg("Invalid proposal state")]
@@ -61,4 +61,160 @@ pub enum ErrorCode {
     
     #[msg("Not a multisig member")]
     NotAMember,
+    
+    #[msg("Invalid proposal action")]
+    InvalidProposalAction,
+    
+    #[msg("Program does not match the proposal target")]
+    InvalidTargetProgram,
+    
+    #[msg("Program data does not match the managed program")]
+    InvalidProgramData,
+    
+    #[msg("Program is not owned by the upgradeable loader")]
+    ProgramNotUpgradeable,
+    
+    #[msg("Member already exists")]
+    DuplicateMember,
+    
+    #[msg("Invalid timelock period")]
+    InvalidTimelock,
+    
+    #[msg("Invalid expiry window")]
+    InvalidExpiryWindow,
+    
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+    
+    #[msg("Proposal has not expired")]
+    ProposalNotExpired,
+    
+    #[msg("No approval to revoke")]
+    ApprovalNotFound,
+    
+    #[msg("Duplicate rejection")]
+    DuplicateRejection,
+    
+    #[msg("Member has already voted the other way")]
+    ConflictingVote,
+    
+    #[msg("System is paused for this operation")]
+    SystemPaused,
+    
+    #[msg("Invalid pause scope")]
+    InvalidPauseScope,
+    
+    #[msg("Pause reason too long")]
+    PauseReasonTooLong,
+    
+    #[msg("Invalid pause window")]
+    InvalidPauseWindow,
+    
+    #[msg("Pause already ratified by this member")]
+    DuplicateRatification,
+    
+    #[msg("Pause has already been ratified")]
+    PauseAlreadyRatified,
+    
+    #[msg("Resume already requested by this member")]
+    DuplicateResumeVote,
+    
+    #[msg("Buffer authority must be the multisig")]
+    BufferAuthorityMismatch,
+    
+    #[msg("Rent recipient must be the proposer")]
+    InvalidRentRecipient,
+    
+    #[msg("Invalid retention period")]
+    InvalidRetentionPeriod,
+    
+    #[msg("Proposal is not executed, cancelled, expired or rejected")]
+    ProposalNotResolved,
+    
+    #[msg("Retention period has not elapsed")]
+    RetentionPeriodActive,
+    
+    #[msg("Upgrade authority does not match the program data")]
+    UpgradeAuthorityMismatch,
+    
+    #[msg("Missing required upgrade authority signature")]
+    MissingAuthoritySignature,
+    
+    #[msg("Program has been finalized and can no longer be upgraded")]
+    ProgramFinalized,
+    
+    #[msg("Invalid governed instruction")]
+    InvalidGovernedInstruction,
+    
+    #[msg("Too many governed instructions")]
+    TooManyInstructions,
+    
+    #[msg("Too many upgrades in one bundle")]
+    TooManyBundledUpgrades,
+    
+    #[msg("Program appears more than once in the upgrade bundle")]
+    DuplicateUpgradeTarget,
+    
+    #[msg("Missing or mismatched accounts for bundled upgrades")]
+    InvalidBundleAccounts,
+    
+    #[msg("Member does not hold the role required for this instruction")]
+    MissingRole,
+    
+    #[msg("Invalid member roles")]
+    InvalidRoles,
+    
+    #[msg("Member weight must be greater than zero")]
+    InvalidWeight,
+    
+    #[msg("Cancel thresholds must be positive and reachable by the cancellers")]
+    InvalidCancelThreshold,
+    
+    #[msg("Cancel reason too long")]
+    CancelReasonTooLong,
+    
+    #[msg("Member has already voted to cancel this proposal")]
+    DuplicateCancellation,
+    
+    #[msg("Invalid emergency threshold or timelock")]
+    InvalidEmergencyConfig,
+    
+    #[msg("No migration registered for this account type and version")]
+    MigrationNotFound,
+    
+    #[msg("Account has not been migrated")]
+    AccountNotMigrated,
+    
+    #[msg("Account data no longer matches the migrated data hash")]
+    AccountDataMismatch,
+    
+    #[msg("Migration must cover at least one account")]
+    InvalidMigrationCount,
+    
+    #[msg("Every expected account has already been migrated")]
+    MigrationCountExceeded,
+    
+    #[msg("Not every expected account has been migrated")]
+    MigrationIncomplete,
+    
+    #[msg("Migration already completed")]
+    MigrationAlreadyCompleted,
+    
+    #[msg("Batch must pass (account, version record) pairs")]
+    InvalidMigrationBatch,
+    
+    #[msg("Signer is neither a migrator nor the account's owner")]
+    UnauthorizedMigrator,
+    
+    #[msg("History and summary accounts must be passed exactly when the proposal is closed")]
+    InvalidArchiveAccounts,
+    
+    #[msg("Buffer appears more than once in the upgrade bundle")]
+    DuplicateBundleBuffer,
+    
+    #[msg("Account data does not match the migration's old layout")]
+    AccountLayoutMismatch,
+    
+    #[msg("Upgrade buffers hold more program bytes than one transaction can hash")]
+    BundleTooLarge,
 }
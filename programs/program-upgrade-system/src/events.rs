@@ -1,25 +1,61 @@
 use anchor_lang::prelude::*;
+use crate::state::{BundledUpgrade, Member, ProposalAction, UpgradeStatus};
 
 #[event]
 pub struct ProposalCreatedEvent {
     pub proposal_id: Pubkey,
+    pub index: u64,
     pub proposer: Pubkey,
+    pub action: ProposalAction,
+    pub target_program: Pubkey,
     pub new_program_buffer: Pubkey,
+    pub buffer_hash: [u8; 32],
+    pub bundled_upgrades: Vec<BundledUpgrade>,
     pub description: String,
     pub timelock_end: i64,
     pub timestamp: i64,
 }
 
+#[event]
+pub struct EmergencyProposalEvent {
+    pub proposal_id: Pubkey,
+    pub proposer: Pubkey,
+    pub target_program: Pubkey,
+    pub emergency_threshold: u16,
+    pub timelock_period: i64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct ApprovalEvent {
     pub proposal_id: Pubkey,
     pub approver: Pubkey,
-    pub approval_count: u8,
-    pub threshold: u8,
+    pub approval_weight: u16,
+    pub threshold: u16,
     pub timelock_activated: bool,
     pub timestamp: i64,
 }
 
+#[event]
+pub struct RejectionEvent {
+    pub proposal_id: Pubkey,
+    pub rejector: Pubkey,
+    pub rejection_weight: u16,
+    pub threshold: u16,
+    pub proposal_rejected: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ApprovalRevokedEvent {
+    pub proposal_id: Pubkey,
+    pub approver: Pubkey,
+    pub approval_weight: u16,
+    pub threshold: u16,
+    pub timelock_reset: bool,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct UpgradeExecutedEvent {
     pub proposal_id: Pubkey,
@@ -28,19 +64,103 @@ pub struct UpgradeExecutedEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct CancelVoteEvent {
+    pub proposal_id: Pubkey,
+    pub canceller: Pubkey,
+    pub reason: String,
+    pub cancel_weight: u16,
+    pub cancel_threshold: u16,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct UpgradeCancelledEvent {
     pub proposal_id: Pubkey,
     pub canceller: Pubkey,
+    pub cancellers: Vec<Pubkey>,
     pub reason: String,
+    pub reclaimed_lamports: u64,
+    pub proposal_closed: bool,
     pub timestamp: i64,
 }
 
 #[event]
 pub struct AccountMigratedEvent {
+    pub proposal_id: Pubkey,
     pub account: Pubkey,
     pub old_version: u8,
     pub new_version: u8,
+    pub migrated_accounts: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationStartedEvent {
+    pub proposal_id: Pubkey,
+    pub tracker: Pubkey,
+    pub total_accounts: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchMigratedEvent {
+    pub proposal_id: Pubkey,
+    pub to_version: u8,
+    pub migrated: u32,
+    pub skipped: u32,
+    pub unprocessed: u32,
+    pub migrated_accounts: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationCompletedEvent {
+    pub proposal_id: Pubkey,
+    pub migrated_accounts: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccountVerifiedEvent {
+    pub account: Pubkey,
+    pub version: u8,
+    pub data_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SystemPausedEvent {
+    pub pauser: Pubkey,
+    pub scopes: u8,
+    pub paused_scopes: u8,
+    pub reason: String,
+    pub expires_at: Option<i64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseRatifiedEvent {
+    pub ratifier: Pubkey,
+    pub ratification_weight: u16,
+    pub threshold: u16,
+    pub ratified: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ResumeVoteEvent {
+    pub voter: Pubkey,
+    pub vote_weight: u16,
+    pub threshold: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SystemResumedEvent {
+    pub resumer: Pubkey,
+    pub scopes: u8,
+    pub paused_scopes: u8,
     pub timestamp: i64,
 }
 
@@ -50,3 +170,69 @@ pub struct TimelockActivatedEvent {
     pub activated_at: i64,
     pub expires_at: i64,
 }
+
+#[event]
+pub struct ProgramRegisteredEvent {
+    pub proposal_id: Pubkey,
+    pub program_id: Pubkey,
+    pub program_data: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramDeregisteredEvent {
+    pub proposal_id: Pubkey,
+    pub program_id: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigChangedEvent {
+    pub proposal_id: Pubkey,
+    pub action: ProposalAction,
+    pub members: Vec<Member>,
+    pub threshold: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalExpiredEvent {
+    pub proposal_id: Pubkey,
+    pub previous_status: UpgradeStatus,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalClosedEvent {
+    pub proposal_id: Pubkey,
+    pub proposer: Pubkey,
+    pub status: UpgradeStatus,
+    pub history_index: u64,
+    pub reclaimed_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UpgradeAuthorityChangedEvent {
+    pub proposal_id: Pubkey,
+    pub program_id: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub checked: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramFinalizedEvent {
+    pub proposal_id: Pubkey,
+    pub program_id: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InstructionsExecutedEvent {
+    pub proposal_id: Pubkey,
+    pub instruction_count: u8,
+    pub executor: Pubkey,
+    pub timestamp: i64,
+}
@@ -28,25 +28,30 @@ pub fn handler(
     ctx: Context<ApproveUpgrade>,
     _proposal_id: Pubkey,
 ) -> Result<()> {
-    validate_multisig_member(&ctx.accounts.multisig_config.members, &ctx.accounts.approver.key())?;
+    validate_member_role(&ctx.accounts.multisig_config, &ctx.accounts.approver.key(), ROLE_APPROVER)?;
+    validate_not_paused(&ctx.accounts.multisig_config, PAUSE_APPROVALS)?;
     
     let proposal = &mut ctx.accounts.proposal;
+    validate_not_expired(proposal)?;
     
     // Check for duplicate approval
     require!(
         !proposal.approvals.contains(&ctx.accounts.approver.key()),
         ErrorCode::DuplicateApproval
     );
+    require!(
+        !proposal.rejections.contains(&ctx.accounts.approver.key()),
+        ErrorCode::ConflictingVote
+    );
     
     // Add approval
     proposal.approvals.push(ctx.accounts.approver.key());
     proposal.approval_count += 1;
     
     let clock = Clock::get()?;
-    let threshold_met = validate_threshold(
-        proposal.approval_count, 
-        ctx.accounts.multisig_config.threshold
-    )?;
+    let approval_weight = tally_member_weight(&ctx.accounts.multisig_config.members, &proposal.approvals);
+    let threshold = required_threshold(proposal, &ctx.accounts.multisig_config);
+    let threshold_met = validate_threshold(approval_weight, threshold)?;
     
     let mut timelock_activated = false;
     
@@ -59,7 +64,9 @@ pub fn handler(
         emit!(TimelockActivatedEvent {
             proposal_id: proposal.id,
             activated_at: clock.unix_timestamp,
-            expires_at: clock.unix_timestamp + proposal.timelock_period,
+            expires_at: clock.unix_timestamp
+                .checked_add(proposal.timelock_period)
+                .ok_or(ErrorCode::MathOverflow)?,
         });
     } else if threshold_met {
         proposal.status = UpgradeStatus::Approved;
@@ -68,8 +75,8 @@ pub fn handler(
     emit!(ApprovalEvent {
         proposal_id: proposal.id,
         approver: ctx.accounts.approver.key(),
-        approval_count: proposal.approval_count,
-        threshold: ctx.accounts.multisig_config.threshold,
+        approval_weight,
+        threshold,
         timelock_activated,
         timestamp: clock.unix_timestamp,
     });
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
 use crate::state::*;
 use crate::error::ErrorCode;
 use crate::events::*;
@@ -11,6 +12,8 @@ pub struct CancelUpgrade<'info> {
         mut,
         constraint = proposal.status != UpgradeStatus::Executed @ ErrorCode::CannotCancelAfterExecution,
         constraint = proposal.status != UpgradeStatus::Cancelled @ ErrorCode::ProposalAlreadyCancelled,
+        constraint = proposal.status != UpgradeStatus::Expired @ ErrorCode::ProposalExpired,
+        constraint = proposal.status != UpgradeStatus::Rejected @ ErrorCode::InvalidProposalState,
     )]
     pub proposal: Box<Account<'info, UpgradeProposal>>,
     
@@ -23,36 +26,161 @@ pub struct CancelUpgrade<'info> {
     #[account(mut)]
     pub canceller: Signer<'info>,
     
-    /// CHECK: Buffer account to close and refund
-    #[account(mut)]
+    /// CHECK: Buffer account to close and refund; only used for upgrade proposals
+    #[account(
+        mut,
+        constraint = proposal.action != ProposalAction::Upgrade
+            || buffer.key() == proposal.new_program_buffer @ ErrorCode::InvalidProgramBuffer,
+    )]
     pub buffer: UncheckedAccount<'info>,
     
-    /// CHECK: Rent recipient
-    #[account(mut)]
+    /// CHECK: Rent recipient, must be the proposer who funded the proposal
+    #[account(
+        mut,
+        address = proposal.proposer @ ErrorCode::InvalidRentRecipient,
+    )]
     pub rent_recipient: UncheckedAccount<'info>,
+    
+    /// CHECK: BPF Loader Upgradeable Program
+    #[account(address = bpf_loader_upgradeable::ID)]
+    pub bpf_loader_upgradeable: UncheckedAccount<'info>,
+    
+    /// Archive counter, required when the cancellation closes the proposal
+    #[account(
+        mut,
+        seeds = [SEED_HISTORY],
+        bump = proposal_history.bump,
+    )]
+    pub proposal_history: Option<Box<Account<'info, ProposalHistory>>>,
+    
+    /// Summary of the closed proposal, required when the cancellation closes the proposal
+    #[account(
+        init,
+        payer = canceller,
+        space = ProposalSummary::LEN,
+        seeds = [SEED_PROPOSAL_SUMMARY, proposal.index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal_summary: Option<Box<Account<'info, ProposalSummary>>>,
+    
+    /// Stored instructions of an `ExecuteInstructions` proposal, closed alongside it
+    #[account(
+        mut,
+        seeds = [SEED_INSTRUCTIONS, proposal.key().as_ref()],
+        bump = proposal_instructions.bump,
+    )]
+    pub proposal_instructions: Option<Box<Account<'info, ProposalInstructions>>>,
+    
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
-    ctx: Context<CancelUpgrade>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CancelUpgrade<'info>>,
     _proposal_id: Pubkey,
+    reason: String,
+    close_proposal: bool,
 ) -> Result<()> {
-    validate_multisig_member(&ctx.accounts.multisig_config.members, &ctx.accounts.canceller.key())?;
+    let canceller = ctx.accounts.canceller.key();
+    validate_member_role(&ctx.accounts.multisig_config, &canceller, ROLE_CANCELLER)?;
+    require!(
+        reason.len() <= MAX_CANCEL_REASON_LENGTH,
+        ErrorCode::CancelReasonTooLong
+    );
     
-    let proposal = &mut ctx.accounts.proposal;
     let clock = Clock::get()?;
+    let multisig = &ctx.accounts.multisig_config;
+    let proposal = &mut ctx.accounts.proposal;
+    
+    require!(
+        !proposal.cancellations.contains(&canceller),
+        ErrorCode::DuplicateCancellation
+    );
+    proposal.cancellations.push(canceller);
+    
+    // The first canceller's reason is the one recorded on the proposal
+    if proposal.cancel_reason.is_empty() {
+        proposal.cancel_reason = reason.clone();
+    }
+    
+    // A proposal that has reached its threshold needs a larger quorum to cancel
+    let cancel_threshold = if proposal.status == UpgradeStatus::Proposed {
+        multisig.cancel_threshold
+    } else {
+        multisig.active_cancel_threshold
+    };
+    let cancel_weight = tally_role_weight(&multisig.members, &proposal.cancellations, ROLE_CANCELLER);
+    
+    emit!(CancelVoteEvent {
+        proposal_id: proposal.id,
+        canceller,
+        reason,
+        cancel_weight,
+        cancel_threshold,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    // Archive accounts are only passed when this vote closes the proposal, so a vote
+    // below quorum cannot leave an empty summary behind
+    let closing = close_proposal && cancel_weight >= cancel_threshold;
+    require!(
+        ctx.accounts.proposal_history.is_some() == closing
+            && ctx.accounts.proposal_summary.is_some() == closing,
+        ErrorCode::InvalidArchiveAccounts
+    );
+    
+    if cancel_weight < cancel_threshold {
+        return Ok(());
+    }
+    
+    // Close the locked buffers and refund their rent. Bundled upgrades pass their
+    // buffers, in order, through remaining_accounts.
+    let reclaimed_lamports = if ctx.accounts.proposal.action == ProposalAction::Upgrade {
+        close_upgrade_buffers(
+            &ctx.accounts.proposal,
+            &ctx.accounts.buffer,
+            ctx.remaining_accounts,
+            &ctx.accounts.rent_recipient,
+            &ctx.accounts.multisig_config,
+        )?
+    } else {
+        0
+    };
+    
+    let proposal = &mut ctx.accounts.proposal;
     
     // Update proposal state
     proposal.status = UpgradeStatus::Cancelled;
-    
-    // Close buffer account and refund rent
-    // In production, you would invoke close buffer instruction via CPI
+    proposal.resolved_at = Some(clock.unix_timestamp);
     
     emit!(UpgradeCancelledEvent {
         proposal_id: proposal.id,
-        canceller: ctx.accounts.canceller.key(),
-        reason: "Cancelled by multisig".to_string(),
+        canceller,
+        cancellers: proposal.cancellations.clone(),
+        reason: proposal.cancel_reason.clone(),
+        reclaimed_lamports,
+        proposal_closed: close_proposal,
         timestamp: clock.unix_timestamp,
     });
     
+    if closing {
+        let rent_recipient = ctx.accounts.rent_recipient.to_account_info();
+        if proposal.action == ProposalAction::ExecuteInstructions {
+            let proposal_instructions = ctx.accounts.proposal_instructions
+                .as_ref()
+                .ok_or(ErrorCode::InvalidGovernedInstruction)?;
+            proposal_instructions.close(rent_recipient.clone())?;
+        }
+        
+        if let (Some(history), Some(summary)) = (
+            ctx.accounts.proposal_history.as_mut(),
+            ctx.accounts.proposal_summary.as_mut(),
+        ) {
+            let bump = ctx.bumps.proposal_summary.ok_or(ErrorCode::InvalidArchiveAccounts)?;
+            archive_proposal(history, summary, proposal, clock.unix_timestamp, bump)?;
+        }
+        proposal.close(rent_recipient)?;
+    }
+    
     Ok(())
 }
+
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
+
+/// Permissionless crank that archives a resolved proposal once its retention
+/// period has passed and refunds the proposal's rent to the proposer. Buffers still
+/// locked by an expired or rejected upgrade are closed and refunded the same way.
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    #[account(
+        mut,
+        close = rent_recipient,
+        constraint = proposal.is_resolved() @ ErrorCode::ProposalNotResolved,
+    )]
+    pub proposal: Box<Account<'info, UpgradeProposal>>,
+    
+    #[account(
+        seeds = [SEED_MULTISIG],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Box<Account<'info, MultisigConfig>>,
+    
+    #[account(
+        mut,
+        seeds = [SEED_HISTORY],
+        bump = proposal_history.bump,
+    )]
+    pub proposal_history: Box<Account<'info, ProposalHistory>>,
+    
+    #[account(
+        init,
+        payer = closer,
+        space = ProposalSummary::LEN,
+        seeds = [SEED_PROPOSAL_SUMMARY, proposal.index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal_summary: Box<Account<'info, ProposalSummary>>,
+    
+    /// Stored instructions of an `ExecuteInstructions` proposal, closed alongside it
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [SEED_INSTRUCTIONS, proposal.key().as_ref()],
+        bump = proposal_instructions.bump,
+    )]
+    pub proposal_instructions: Option<Box<Account<'info, ProposalInstructions>>>,
+    
+    /// CHECK: Primary buffer of an expired or rejected upgrade, checked against the proposal
+    #[account(mut)]
+    pub buffer: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: BPF Loader Upgradeable Program, required with `buffer`
+    #[account(address = bpf_loader_upgradeable::ID)]
+    pub bpf_loader_upgradeable: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Rent recipient, must be the proposer who funded the proposal
+    #[account(
+        mut,
+        address = proposal.proposer @ ErrorCode::InvalidRentRecipient,
+    )]
+    pub rent_recipient: UncheckedAccount<'info>,
+    
+    /// Pays for the proposal's summary account
+    #[account(mut)]
+    pub closer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CloseProposal<'info>>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    let clock = Clock::get()?;
+    
+    require!(
+        proposal.action != ProposalAction::ExecuteInstructions
+            || ctx.accounts.proposal_instructions.is_some(),
+        ErrorCode::InvalidGovernedInstruction
+    );
+    
+    let resolved_at = proposal.resolved_at.ok_or(ErrorCode::ProposalNotResolved)?;
+    let retained_until = resolved_at
+        .checked_add(ctx.accounts.multisig_config.retention_period)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        clock.unix_timestamp >= retained_until,
+        ErrorCode::RetentionPeriodActive
+    );
+    
+    let mut reclaimed_lamports = proposal.to_account_info().lamports();
+    // Bundled upgrades pass their buffers, in order, through remaining_accounts
+    if proposal.holds_locked_buffers() {
+        let buffer = ctx.accounts.buffer.as_ref().ok_or(ErrorCode::InvalidProgramBuffer)?;
+        require!(
+            ctx.accounts.bpf_loader_upgradeable.is_some(),
+            ErrorCode::InvalidProgramBuffer
+        );
+        reclaimed_lamports += close_upgrade_buffers(
+            proposal,
+            buffer,
+            ctx.remaining_accounts,
+            &ctx.accounts.rent_recipient,
+            &ctx.accounts.multisig_config,
+        )?;
+    }
+    
+    let history_index = archive_proposal(
+        &mut ctx.accounts.proposal_history,
+        &mut ctx.accounts.proposal_summary,
+        proposal,
+        resolved_at,
+        ctx.bumps.proposal_summary,
+    )?;
+    
+    emit!(ProposalClosedEvent {
+        proposal_id: proposal.id,
+        proposer: proposal.proposer,
+        status: proposal.status,
+        history_index,
+        reclaimed_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct DeregisterProgram<'info> {
+    #[account(
+        mut,
+        constraint = proposal.status == UpgradeStatus::TimelockActive @ ErrorCode::InvalidProposalState,
+        constraint = proposal.action == ProposalAction::DeregisterProgram @ ErrorCode::InvalidProposalAction,
+    )]
+    pub proposal: Box<Account<'info, UpgradeProposal>>,
+    
+    #[account(
+        seeds = [SEED_MULTISIG],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Box<Account<'info, MultisigConfig>>,
+    
+    #[account(
+        mut,
+        close = executor,
+        seeds = [SEED_MANAGED_PROGRAM, proposal.target_program.as_ref()],
+        bump = managed_program.bump,
+    )]
+    pub managed_program: Box<Account<'info, ManagedProgram>>,
+    
+    #[account(mut)]
+    pub executor: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<DeregisterProgram>) -> Result<()> {
+    validate_member_role(&ctx.accounts.multisig_config, &ctx.accounts.executor.key(), ROLE_EXECUTOR)?;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    validate_ready_for_execution(proposal, &ctx.accounts.multisig_config)?;
+    
+    let clock = Clock::get()?;
+    
+    proposal.status = UpgradeStatus::Executed;
+    proposal.executed_at = Some(clock.unix_timestamp);
+    proposal.resolved_at = Some(clock.unix_timestamp);
+    
+    emit!(ProgramDeregisteredEvent {
+        proposal_id: proposal.id,
+        program_id: ctx.accounts.managed_program.program_id,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    bpf_loader_upgradeable,
+    program::invoke_signed,
+};
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct ExecuteAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        constraint = proposal.status == UpgradeStatus::TimelockActive @ ErrorCode::InvalidProposalState,
+        constraint = proposal.action.is_authority_transfer() @ ErrorCode::InvalidProposalAction,
+    )]
+    pub proposal: Box<Account<'info, UpgradeProposal>>,
+    
+    #[account(
+        seeds = [SEED_MULTISIG],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Box<Account<'info, MultisigConfig>>,
+    
+    #[account(
+        seeds = [SEED_MANAGED_PROGRAM, proposal.target_program.as_ref()],
+        bump = managed_program.bump,
+    )]
+    pub managed_program: Box<Account<'info, ManagedProgram>>,
+    
+    /// CHECK: Program data account of the managed program
+    #[account(
+        mut,
+        address = managed_program.program_data @ ErrorCode::InvalidProgramData,
+    )]
+    pub program_data: UncheckedAccount<'info>,
+    
+    /// CHECK: Current upgrade authority, checked against the program data in the handler.
+    /// Either the multisig PDA or, when onboarding, a key that signs this transaction.
+    pub current_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: New upgrade authority named in the proposal; must sign for the checked
+    /// variant unless it is the multisig PDA
+    pub new_authority: UncheckedAccount<'info>,
+    
+    pub executor: Signer<'info>,
+    
+    /// CHECK: BPF Loader Upgradeable Program
+    #[account(address = bpf_loader_upgradeable::ID)]
+    pub bpf_loader_upgradeable: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<ExecuteAuthorityTransfer>) -> Result<()> {
+    validate_member_role(&ctx.accounts.multisig_config, &ctx.accounts.executor.key(), ROLE_EXECUTOR)?;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    validate_ready_for_execution(proposal, &ctx.accounts.multisig_config)?;
+    
+    let (new_authority, checked) = match proposal.action {
+        ProposalAction::SetUpgradeAuthority { new_authority } => (new_authority, false),
+        ProposalAction::SetUpgradeAuthorityChecked { new_authority } => (new_authority, true),
+        _ => return err!(ErrorCode::InvalidProposalAction),
+    };
+    require!(
+        ctx.accounts.new_authority.key() == new_authority,
+        ErrorCode::UpgradeAuthorityMismatch
+    );
+    
+    let multisig_key = ctx.accounts.multisig_config.key();
+    let previous_authority = read_upgrade_authority(&ctx.accounts.program_data)?
+        .ok_or(ErrorCode::UpgradeAuthorityMismatch)?;
+    require!(
+        ctx.accounts.current_authority.key() == previous_authority,
+        ErrorCode::UpgradeAuthorityMismatch
+    );
+    
+    // The multisig signs for its own side of the transfer. Moving authority in from
+    // another key is only possible with the checked variant, co-signed by that key.
+    if previous_authority != multisig_key {
+        require!(
+            checked && new_authority == multisig_key,
+            ErrorCode::UpgradeAuthorityMismatch
+        );
+        require!(
+            ctx.accounts.current_authority.is_signer,
+            ErrorCode::MissingAuthoritySignature
+        );
+    }
+    if checked && new_authority != multisig_key {
+        require!(
+            ctx.accounts.new_authority.is_signer,
+            ErrorCode::MissingAuthoritySignature
+        );
+    }
+    
+    let program_id = ctx.accounts.managed_program.program_id;
+    let set_authority_instruction = if checked {
+        bpf_loader_upgradeable::set_upgrade_authority_checked(
+            &program_id,
+            &previous_authority,
+            &new_authority,
+        )
+    } else {
+        bpf_loader_upgradeable::set_upgrade_authority(
+            &program_id,
+            &previous_authority,
+            Some(&new_authority),
+        )
+    };
+    
+    let multisig_seeds = &[
+        SEED_MULTISIG,
+        &[ctx.accounts.multisig_config.bump],
+    ];
+    
+    invoke_signed(
+        &set_authority_instruction,
+        &[
+            ctx.accounts.program_data.to_account_info(),
+            ctx.accounts.current_authority.to_account_info(),
+            ctx.accounts.new_authority.to_account_info(),
+            ctx.accounts.multisig_config.to_account_info(),
+        ],
+        &[multisig_seeds],
+    )?;
+    
+    let clock = Clock::get()?;
+    
+    proposal.status = UpgradeStatus::Executed;
+    proposal.executed_at = Some(clock.unix_timestamp);
+    proposal.resolved_at = Some(clock.unix_timestamp);
+    
+    emit!(UpgradeAuthorityChangedEvent {
+        proposal_id: proposal.id,
+        program_id,
+        previous_authority,
+        new_authority,
+        checked,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    #[account(
+        mut,
+        constraint = proposal.status == UpgradeStatus::TimelockActive @ ErrorCode::InvalidProposalState,
+        constraint = proposal.action.is_config_change() @ ErrorCode::InvalidProposalAction,
+    )]
+    pub proposal: Box<Account<'info, UpgradeProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [SEED_MULTISIG],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Box<Account<'info, MultisigConfig>>,
+    
+    pub executor: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+    validate_member_role(&ctx.accounts.multisig_config, &ctx.accounts.executor.key(), ROLE_EXECUTOR)?;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    let multisig = &mut ctx.accounts.multisig_config;
+    
+    validate_ready_for_execution(proposal, multisig)?;
+    
+    // Membership may have changed since the proposal was created
+    validate_config_change(multisig, &proposal.action)?;
+    
+    match proposal.action {
+        ProposalAction::AddMember { member, roles, weight } => {
+            multisig.members.push(Member { key: member, roles, weight });
+        }
+        ProposalAction::RemoveMember { member } => {
+            multisig.members.retain(|m| m.key != member);
+        }
+        ProposalAction::SetMemberRoles { member, roles } => {
+            if let Some(existing) = multisig.members.iter_mut().find(|m| m.key == member) {
+                existing.roles = roles;
+            }
+        }
+        ProposalAction::SetMemberWeight { member, weight } => {
+            if let Some(existing) = multisig.members.iter_mut().find(|m| m.key == member) {
+                existing.weight = weight;
+            }
+        }
+        ProposalAction::ChangeThreshold { threshold } => {
+            multisig.threshold = threshold;
+        }
+        ProposalAction::SetTimelock { min_timelock, default_timelock } => {
+            multisig.min_timelock = min_timelock;
+            multisig.default_timelock = default_timelock;
+        }
+        ProposalAction::SetExpiryWindows { approval_window, execution_window } => {
+            multisig.approval_window = approval_window;
+            multisig.execution_window = execution_window;
+        }
+        ProposalAction::SetPauseWindow { pause_window } => {
+            multisig.pause_window = pause_window;
+        }
+        ProposalAction::SetRetentionPeriod { retention_period } => {
+            multisig.retention_period = retention_period;
+        }
+        ProposalAction::SetCancelThresholds { cancel_threshold, active_cancel_threshold } => {
+            multisig.cancel_threshold = cancel_threshold;
+            multisig.active_cancel_threshold = active_cancel_threshold;
+        }
+        ProposalAction::SetEmergencyConfig { emergency_threshold, emergency_timelock } => {
+            multisig.emergency_threshold = emergency_threshold;
+            multisig.emergency_timelock = emergency_timelock;
+        }
+        _ => return err!(ErrorCode::InvalidProposalAction),
+    }
+    
+    let clock = Clock::get()?;
+    
+    proposal.status = UpgradeStatus::Executed;
+    proposal.executed_at = Some(clock.unix_timestamp);
+    proposal.resolved_at = Some(clock.unix_timestamp);
+    
+    emit!(ConfigChangedEvent {
+        proposal_id: proposal.id,
+        action: proposal.action,
+        members: multisig.members.clone(),
+        threshold: multisig.threshold,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    program::invoke_signed,
+};
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
+
+/// Replays a proposal's stored instructions with the multisig PDA as signer. Every
+/// account the instructions reference, including the programs they call, is passed
+/// through `remaining_accounts`.
+#[derive(Accounts)]
+pub struct ExecuteInstructions<'info> {
+    #[account(
+        mut,
+        constraint = proposal.status == UpgradeStatus::TimelockActive @ ErrorCode::InvalidProposalState,
+        constraint = proposal.action == ProposalAction::ExecuteInstructions @ ErrorCode::InvalidProposalAction,
+    )]
+    pub proposal: Box<Account<'info, UpgradeProposal>>,
+    
+    #[account(
+        seeds = [SEED_MULTISIG],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Box<Account<'info, MultisigConfig>>,
+    
+    #[account(
+        seeds = [SEED_INSTRUCTIONS, proposal.key().as_ref()],
+        bump = proposal_instructions.bump,
+    )]
+    pub proposal_instructions: Box<Account<'info, ProposalInstructions>>,
+    
+    pub executor: Signer<'info>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteInstructions<'info>>) -> Result<()> {
+    validate_member_role(&ctx.accounts.multisig_config, &ctx.accounts.executor.key(), ROLE_EXECUTOR)?;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    validate_ready_for_execution(proposal, &ctx.accounts.multisig_config)?;
+    
+    let multisig_seeds = &[
+        SEED_MULTISIG,
+        &[ctx.accounts.multisig_config.bump],
+    ];
+    
+    let mut account_infos = ctx.remaining_accounts.to_vec();
+    account_infos.push(ctx.accounts.multisig_config.to_account_info());
+    
+    let instructions = &ctx.accounts.proposal_instructions.instructions;
+    for governed_instruction in instructions.iter() {
+        let instruction = Instruction::from(governed_instruction);
+        invoke_signed(&instruction, &account_infos, &[multisig_seeds])?;
+    }
+    
+    let clock = Clock::get()?;
+    
+    proposal.status = UpgradeStatus::Executed;
+    proposal.executed_at = Some(clock.unix_timestamp);
+    proposal.resolved_at = Some(clock.unix_timestamp);
+    
+    emit!(InstructionsExecutedEvent {
+        proposal_id: proposal.id,
+        instruction_count: instructions.len() as u8,
+        executor: ctx.accounts.executor.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
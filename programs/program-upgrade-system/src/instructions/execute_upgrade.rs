@@ -14,6 +14,7 @@ pub struct ExecuteUpgrade<'info> {
     #[account(
         mut,
         constraint = proposal.status == UpgradeStatus::TimelockActive @ ErrorCode::InvalidProposalState,
+        constraint = proposal.action == ProposalAction::Upgrade @ ErrorCode::InvalidProposalAction,
     )]
     pub proposal: Box<Account<'info, UpgradeProposal>>,
     
@@ -23,12 +24,25 @@ pub struct ExecuteUpgrade<'info> {
     )]
     pub multisig_config: Box<Account<'info, MultisigConfig>>,
     
+    #[account(
+        seeds = [SEED_MANAGED_PROGRAM, proposal.target_program.as_ref()],
+        bump = managed_program.bump,
+        constraint = !managed_program.finalized @ ErrorCode::ProgramFinalized,
+    )]
+    pub managed_program: Box<Account<'info, ManagedProgram>>,
+    
     /// CHECK: This is the program to upgrade
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = program_to_upgrade.key() == proposal.target_program @ ErrorCode::InvalidTargetProgram,
+    )]
     pub program_to_upgrade: UncheckedAccount<'info>,
     
     /// CHECK: This is the program data account
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = program_data.key() == managed_program.program_data @ ErrorCode::InvalidProgramData,
+    )]
     pub program_data: UncheckedAccount<'info>,
     
     /// CHECK: This is the buffer account with new program
@@ -42,6 +56,7 @@ pub struct ExecuteUpgrade<'info> {
     pub executor: Signer<'info>,
     
     /// CHECK: BPF Loader Upgradeable Program
+    #[account(address = bpf_loader_upgradeable::ID)]
     pub bpf_loader_upgradeable: UncheckedAccount<'info>,
     
     pub rent: Sysvar<'info, Rent>,
@@ -49,71 +64,135 @@ pub struct ExecuteUpgrade<'info> {
     pub clock: Sysvar<'info, Clock>,
 }
 
-pub fn handler(
-    ctx: Context<ExecuteUpgrade>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteUpgrade<'info>>,
     _proposal_id: Pubkey,
 ) -> Result<()> {
-    let proposal = &mut ctx.accounts.proposal;
+    validate_member_role(&ctx.accounts.multisig_config, &ctx.accounts.executor.key(), ROLE_EXECUTOR)?;
     
-    // Verify timelock expired
-    let timelock_activated = proposal.timelock_activated_at
-        .ok_or(ErrorCode::InvalidProposalState)?;
+    let proposal = &ctx.accounts.proposal;
     
-    validate_timelock_expired(timelock_activated, proposal.timelock_period)?;
+    // Verify timelock expired and threshold met
+    validate_ready_for_execution(proposal, &ctx.accounts.multisig_config)?;
     
-    // Verify threshold met
+    // Verify buffer matches proposal
     require!(
-        validate_threshold(proposal.approval_count, ctx.accounts.multisig_config.threshold)?,
-        ErrorCode::InsufficientApprovals
+        ctx.accounts.buffer.key() == proposal.new_program_buffer,
+        ErrorCode::InvalidProgramBuffer
     );
     
-    // Verify buffer matches proposal
+    // Verify buffer contents are unchanged since the proposal was approved
+    let (buffer_hash, buffer_len) = read_locked_buffer(
+        &ctx.accounts.buffer,
+        &ctx.accounts.multisig_config.key(),
+    )?;
     require!(
-        ctx.accounts.buffer.key() == proposal.new_program_buffer,
+        buffer_hash == proposal.buffer_hash && buffer_len == proposal.buffer_len,
         ErrorCode::InvalidProgramBuffer
     );
     
+    // Bundled upgrades pass [program, program_data, buffer, managed_program] each
+    require!(
+        ctx.remaining_accounts.len() == proposal.bundled_upgrades.len() * 4,
+        ErrorCode::InvalidBundleAccounts
+    );
+    
     let clock = Clock::get()?;
+    let executor = ctx.accounts.executor.key();
+    
+    upgrade_program(
+        ctx.accounts,
+        &ctx.accounts.program_to_upgrade,
+        &ctx.accounts.program_data,
+        &ctx.accounts.buffer,
+    )?;
+    emit!(UpgradeExecutedEvent {
+        proposal_id: ctx.accounts.proposal.id,
+        program_id: ctx.accounts.program_to_upgrade.key(),
+        executor,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    // Any failed check or CPI below fails the whole transaction, primary upgrade included
+    for (upgrade, accounts) in ctx.accounts.proposal.bundled_upgrades.iter().zip(ctx.remaining_accounts.chunks(4)) {
+        let (program, program_data, buffer, managed_program) =
+            (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+        
+        let managed = load_managed_program(managed_program, &upgrade.target_program)?;
+        require!(!managed.finalized, ErrorCode::ProgramFinalized);
+        require!(
+            program.key() == upgrade.target_program,
+            ErrorCode::InvalidTargetProgram
+        );
+        require!(
+            program_data.key() == managed.program_data,
+            ErrorCode::InvalidProgramData
+        );
+        require!(
+            buffer.key() == upgrade.buffer,
+            ErrorCode::InvalidProgramBuffer
+        );
+        
+        let (buffer_hash, buffer_len) = read_locked_buffer(
+            buffer,
+            &ctx.accounts.multisig_config.key(),
+        )?;
+        require!(
+            buffer_hash == upgrade.buffer_hash && buffer_len == upgrade.buffer_len,
+            ErrorCode::InvalidProgramBuffer
+        );
+        
+        upgrade_program(ctx.accounts, program, program_data, buffer)?;
+        emit!(UpgradeExecutedEvent {
+            proposal_id: ctx.accounts.proposal.id,
+            program_id: upgrade.target_program,
+            executor,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    
+    // Update proposal state
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.status = UpgradeStatus::Executed;
+    proposal.executed_at = Some(clock.unix_timestamp);
+    proposal.resolved_at = Some(clock.unix_timestamp);
     
-    // Execute upgrade via CPI to BPF Loader Upgradeable
-    // NOTE: In production, this requires proper authority setup
-    // The multisig PDA should be the upgrade authority
+    Ok(())
+}
+
+/// Upgrades one program from its buffer via CPI to the BPF Loader Upgradeable, signed by
+/// the multisig PDA as upgrade authority.
+fn upgrade_program<'info>(
+    accounts: &ExecuteUpgrade<'info>,
+    program: &AccountInfo<'info>,
+    program_data: &AccountInfo<'info>,
+    buffer: &AccountInfo<'info>,
+) -> Result<()> {
     let upgrade_instruction = bpf_loader_upgradeable::upgrade(
-        &ctx.accounts.program_to_upgrade.key(),
-        &ctx.accounts.buffer.key(),
-        &ctx.accounts.multisig_config.key(),
-        &ctx.accounts.spill_account.key(),
+        &program.key(),
+        &buffer.key(),
+        &accounts.multisig_config.key(),
+        &accounts.spill_account.key(),
     );
     
     let multisig_seeds = &[
         SEED_MULTISIG,
-        &[ctx.accounts.multisig_config.bump],
+        &[accounts.multisig_config.bump],
     ];
     
     invoke_signed(
         &upgrade_instruction,
         &[
-            ctx.accounts.program_data.to_account_info(),
-            ctx.accounts.program_to_upgrade.to_account_info(),
-            ctx.accounts.buffer.to_account_info(),
-            ctx.accounts.spill_account.to_account_info(),
-            ctx.accounts.rent.to_account_info(),
-            ctx.accounts.clock.to_account_info(),
-            ctx.accounts.multisig_config.to_account_info(),
+            program_data.clone(),
+            program.clone(),
+            buffer.clone(),
+            accounts.spill_account.to_account_info(),
+            accounts.rent.to_account_info(),
+            accounts.clock.to_account_info(),
+            accounts.multisig_config.to_account_info(),
         ],
         &[multisig_seeds],
     )?;
     
-    // Update proposal state
-    proposal.status = UpgradeStatus::Executed;
-    proposal.executed_at = Some(clock.unix_timestamp);
-    
-    emit!(UpgradeExecutedEvent {
-        proposal_id: proposal.id,
-        program_id: ctx.accounts.program_to_upgrade.key(),
-        executor: ctx.accounts.executor.key(),
-        timestamp: clock.unix_timestamp,
-    });
-    
     Ok(())
 }
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::*;
+use crate::utils::*;
+
+/// Permissionless crank that moves a lapsed proposal to `Expired`.
+#[derive(Accounts)]
+pub struct ExpireProposal<'info> {
+    #[account(mut)]
+    pub proposal: Box<Account<'info, UpgradeProposal>>,
+}
+
+pub fn handler(ctx: Context<ExpireProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let clock = Clock::get()?;
+    
+    require!(
+        is_proposal_expired(proposal, clock.unix_timestamp)?,
+        ErrorCode::ProposalNotExpired
+    );
+    
+    let previous_status = proposal.status;
+    proposal.status = UpgradeStatus::Expired;
+    proposal.resolved_at = Some(clock.unix_timestamp);
+    
+    emit!(ProposalExpiredEvent {
+        proposal_id: proposal.id,
+        previous_status,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
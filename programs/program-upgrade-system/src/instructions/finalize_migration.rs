@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct FinalizeMigration<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_MIGRATION_TRACKER, migration_tracker.proposal_id.as_ref()],
+        bump = migration_tracker.bump,
+        constraint = migration_tracker.completed_at.is_none() @ ErrorCode::MigrationAlreadyCompleted,
+    )]
+    pub migration_tracker: Account<'info, MigrationTracker>,
+    
+    #[account(
+        seeds = [SEED_MULTISIG],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Box<Account<'info, MultisigConfig>>,
+    
+    pub migrator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<FinalizeMigration>) -> Result<()> {
+    validate_member_role(&ctx.accounts.multisig_config, &ctx.accounts.migrator.key(), ROLE_MIGRATOR)?;
+    
+    let tracker = &mut ctx.accounts.migration_tracker;
+    require!(
+        tracker.migrated_accounts == tracker.total_accounts,
+        ErrorCode::MigrationIncomplete
+    );
+    
+    let clock = Clock::get()?;
+    tracker.completed_at = Some(clock.unix_timestamp);
+    
+    emit!(MigrationCompletedEvent {
+        proposal_id: tracker.proposal_id,
+        migrated_accounts: tracker.migrated_accounts,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
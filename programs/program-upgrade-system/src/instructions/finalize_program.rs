@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    bpf_loader_upgradeable,
+    program::invoke_signed,
+};
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct FinalizeProgram<'info> {
+    #[account(
+        mut,
+        constraint = proposal.status == UpgradeStatus::TimelockActive @ ErrorCode::InvalidProposalState,
+        constraint = proposal.action == ProposalAction::FinalizeProgram @ ErrorCode::InvalidProposalAction,
+    )]
+    pub proposal: Box<Account<'info, UpgradeProposal>>,
+    
+    #[account(
+        seeds = [SEED_MULTISIG],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Box<Account<'info, MultisigConfig>>,
+    
+    #[account(
+        mut,
+        seeds = [SEED_MANAGED_PROGRAM, proposal.target_program.as_ref()],
+        bump = managed_program.bump,
+        constraint = !managed_program.finalized @ ErrorCode::ProgramFinalized,
+    )]
+    pub managed_program: Box<Account<'info, ManagedProgram>>,
+    
+    /// CHECK: Program data account of the managed program
+    #[account(
+        mut,
+        address = managed_program.program_data @ ErrorCode::InvalidProgramData,
+    )]
+    pub program_data: UncheckedAccount<'info>,
+    
+    pub executor: Signer<'info>,
+    
+    /// CHECK: BPF Loader Upgradeable Program
+    #[account(address = bpf_loader_upgradeable::ID)]
+    pub bpf_loader_upgradeable: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<FinalizeProgram>) -> Result<()> {
+    validate_member_role(&ctx.accounts.multisig_config, &ctx.accounts.executor.key(), ROLE_EXECUTOR)?;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    validate_ready_for_execution(proposal, &ctx.accounts.multisig_config)?;
+    
+    // Only the multisig can renounce; the program must already be under its authority
+    let multisig_key = ctx.accounts.multisig_config.key();
+    require!(
+        read_upgrade_authority(&ctx.accounts.program_data)? == Some(multisig_key),
+        ErrorCode::UpgradeAuthorityMismatch
+    );
+    
+    let managed_program = &mut ctx.accounts.managed_program;
+    let finalize_instruction = bpf_loader_upgradeable::set_upgrade_authority(
+        &managed_program.program_id,
+        &multisig_key,
+        None,
+    );
+    
+    let multisig_seeds = &[
+        SEED_MULTISIG,
+        &[ctx.accounts.multisig_config.bump],
+    ];
+    
+    invoke_signed(
+        &finalize_instruction,
+        &[
+            ctx.accounts.program_data.to_account_info(),
+            ctx.accounts.multisig_config.to_account_info(),
+        ],
+        &[multisig_seeds],
+    )?;
+    
+    let clock = Clock::get()?;
+    
+    managed_program.finalized = true;
+    
+    proposal.status = UpgradeStatus::Executed;
+    proposal.executed_at = Some(clock.unix_timestamp);
+    proposal.resolved_at = Some(clock.unix_timestamp);
+    
+    emit!(ProgramFinalizedEvent {
+        proposal_id: proposal.id,
+        program_id: managed_program.program_id,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
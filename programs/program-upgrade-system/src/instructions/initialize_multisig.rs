@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::ErrorCode;
 use crate::constants::*;
+use crate::utils::*;
 
 #[derive(Accounts)]
 pub struct InitializeMultisig<'info> {
@@ -14,6 +15,15 @@ pub struct InitializeMultisig<'info> {
     )]
     pub multisig_config: Box<Account<'info, MultisigConfig>>,
     
+    #[account(
+        init,
+        payer = authority,
+        space = ProposalHistory::LEN,
+        seeds = [SEED_HISTORY],
+        bump
+    )]
+    pub proposal_history: Box<Account<'info, ProposalHistory>>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
@@ -22,25 +32,55 @@ pub struct InitializeMultisig<'info> {
 
 pub fn handler(
     ctx: Context<InitializeMultisig>,
-    members: Vec<Pubkey>,
-    threshold: u8,
+    members: Vec<Member>,
+    threshold: u16,
+    min_timelock: i64,
+    default_timelock: i64,
+    approval_window: i64,
+    execution_window: i64,
 ) -> Result<()> {
     require!(
         members.len() <= MAX_MULTISIG_MEMBERS,
         ErrorCode::TooManyMembers
     );
     
-    require!(
-        threshold > 0 && threshold as usize <= members.len(),
-        ErrorCode::InvalidThreshold
-    );
+    for (i, member) in members.iter().enumerate() {
+        validate_roles(member.roles)?;
+        validate_weight(member.weight)?;
+        require!(
+            !members[..i].iter().any(|other| other.key == member.key),
+            ErrorCode::DuplicateMember
+        );
+    }
+    
+    // Once approved, cancelling needs the same weight as approving by default
+    validate_quorums(&members, threshold, 0, DEFAULT_CANCEL_THRESHOLD, threshold)?;
+    
+    validate_timelock_bounds(min_timelock, default_timelock)?;
+    validate_expiry_windows(approval_window, execution_window)?;
     
     let multisig = &mut ctx.accounts.multisig_config;
     multisig.authority = ctx.accounts.authority.key();
     multisig.members = members;
     multisig.threshold = threshold;
-    multisig.is_paused = false;
+    multisig.cancel_threshold = DEFAULT_CANCEL_THRESHOLD;
+    multisig.active_cancel_threshold = threshold;
+    multisig.emergency_threshold = 0;
+    multisig.emergency_timelock = DEFAULT_EMERGENCY_TIMELOCK.min(min_timelock);
+    multisig.paused_scopes = 0;
+    multisig.min_timelock = min_timelock;
+    multisig.default_timelock = default_timelock;
+    multisig.approval_window = approval_window;
+    multisig.execution_window = execution_window;
+    multisig.pause_window = DEFAULT_PAUSE_WINDOW;
+    multisig.retention_period = DEFAULT_RETENTION_PERIOD;
+    multisig.proposal_count = 0;
+    multisig.pause = None;
     multisig.bump = ctx.bumps.multisig_config;
     
+    let history = &mut ctx.accounts.proposal_history;
+    history.total_archived = 0;
+    history.bump = ctx.bumps.proposal_history;
+    
     Ok(())
 }
@@ -3,12 +3,14 @@ use crate::state::*;
 use crate::error::ErrorCode;
 use crate::events::*;
 use crate::constants::*;
+use crate::utils::*;
+use crate::migrations::MigrationWriter;
 
 #[derive(Accounts)]
 #[instruction(old_account_key: Pubkey)]
 pub struct MigrateAccount<'info> {
     #[account(
-        init,
+        init_if_needed,
         payer = migrator,
         space = AccountVersion::LEN,
         seeds = [SEED_MIGRATION, old_account_key.as_ref()],
@@ -16,10 +18,42 @@ pub struct MigrateAccount<'info> {
     )]
     pub account_version: Account<'info, AccountVersion>,
     
-    /// CHECK: The account to migrate
-    #[account(mut)]
+    #[account(
+        seeds = [SEED_MULTISIG],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Account<'info, MultisigConfig>,
+    
+    #[account(
+        constraint = proposal.upgrades(&target_program.key()) @ ErrorCode::InvalidTargetProgram,
+    )]
+    pub proposal: Box<Account<'info, UpgradeProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [SEED_MIGRATION_TRACKER, proposal.key().as_ref()],
+        bump = migration_tracker.bump,
+        constraint = migration_tracker.completed_at.is_none() @ ErrorCode::MigrationAlreadyCompleted,
+    )]
+    pub migration_tracker: Account<'info, MigrationTracker>,
+    
+    /// CHECK: The account to migrate, owned by the governed program
+    #[account(
+        mut,
+        owner = target_program.key() @ ErrorCode::InvalidTargetProgram,
+    )]
     pub old_account: UncheckedAccount<'info>,
     
+    /// CHECK: Governed program that owns the account and writes the migrated data
+    #[account(executable)]
+    pub target_program: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [SEED_MANAGED_PROGRAM, target_program.key().as_ref()],
+        bump = managed_program.bump,
+    )]
+    pub managed_program: Account<'info, ManagedProgram>,
+    
     #[account(mut)]
     pub migrator: Signer<'info>,
     
@@ -29,44 +63,65 @@ pub struct MigrateAccount<'info> {
 pub fn handler(
     ctx: Context<MigrateAccount>,
     old_account_key: Pubkey,
+    to_version: u8,
 ) -> Result<()> {
-    let account_version = &mut ctx.accounts.account_version;
-    let old_account = &ctx.accounts.old_account;
+    validate_not_paused(&ctx.accounts.multisig_config, PAUSE_MIGRATION)?;
     
     // Verify old_account_key matches
     require!(
-        old_account.key() == old_account_key,
+        ctx.accounts.old_account.key() == old_account_key,
         ErrorCode::InvalidAccountVersion
     );
     
     let clock = Clock::get()?;
     
-    // Read old account data
-    let old_data = old_account.try_borrow_data()?;
+    // A freshly created record means the account has never been migrated
+    let from_version = match ctx.accounts.account_version.version {
+        0 => INITIAL_ACCOUNT_VERSION,
+        version => version,
+    };
+    require!(
+        from_version != to_version,
+        ErrorCode::AccountAlreadyMigrated
+    );
     
-    // Create a simple hash by taking first 32 bytes or padding with zeros
-    let mut old_data_hash = [0u8; 32];
-    let copy_len = old_data.len().min(32);
-    old_data_hash[..copy_len].copy_from_slice(&old_data[..copy_len]);
+    let target_program = ctx.accounts.target_program.to_account_info();
+    let migrator = ctx.accounts.migrator.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let writer = MigrationWriter {
+        multisig_config: &ctx.accounts.multisig_config,
+        target_program: &target_program,
+        migrator: &migrator,
+        system_program: &system_program,
+    };
+    let (old_data, new_data) = writer.migrate(
+        &ctx.accounts.old_account.to_account_info(),
+        from_version,
+        to_version,
+    )?;
     
-    // In a real implementation, you would:
-    // 1. Deserialize old data structure
-    // 2. Transform to new data structure
-    // 3. Realloc account if needed
-    // 4. Serialize new data back
+    let tracker = &mut ctx.accounts.migration_tracker;
+    require!(
+        tracker.migrated_accounts < tracker.total_accounts,
+        ErrorCode::MigrationCountExceeded
+    );
+    tracker.migrated_accounts += 1;
     
-    // For this example, we just track the migration
-    account_version.account = old_account.key();
-    account_version.version = 2; // New version
-    account_version.migrated = true;
-    account_version.migrated_at = Some(clock.unix_timestamp);
-    account_version.old_data_hash = old_data_hash;
-    account_version.new_data_hash = old_data_hash; // Would be different after real migration
+    record_migration(
+        &mut ctx.accounts.account_version,
+        old_account_key,
+        to_version,
+        clock.unix_timestamp,
+        &old_data,
+        &new_data,
+    );
     
     emit!(AccountMigratedEvent {
-        account: old_account.key(),
-        old_version: 1,
-        new_version: 2,
+        proposal_id: tracker.proposal_id,
+        account: old_account_key,
+        old_version: from_version,
+        new_version: to_version,
+        migrated_accounts: tracker.migrated_accounts,
         timestamp: clock.unix_timestamp,
     });
     
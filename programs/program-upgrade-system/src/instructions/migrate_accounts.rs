@@ -0,0 +1,227 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
+use crate::migrations::MigrationWriter;
+
+#[derive(Accounts)]
+pub struct MigrateAccounts<'info> {
+    #[account(
+        seeds = [SEED_MULTISIG],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Account<'info, MultisigConfig>,
+    
+    #[account(
+        constraint = proposal.upgrades(&target_program.key()) @ ErrorCode::InvalidTargetProgram,
+    )]
+    pub proposal: Box<Account<'info, UpgradeProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [SEED_MIGRATION_TRACKER, proposal.key().as_ref()],
+        bump = migration_tracker.bump,
+        constraint = migration_tracker.completed_at.is_none() @ ErrorCode::MigrationAlreadyCompleted,
+    )]
+    pub migration_tracker: Account<'info, MigrationTracker>,
+    
+    /// CHECK: Governed program that owns the accounts and writes the migrated data
+    #[account(executable)]
+    pub target_program: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [SEED_MANAGED_PROGRAM, target_program.key().as_ref()],
+        bump = managed_program.bump,
+    )]
+    pub managed_program: Account<'info, ManagedProgram>,
+    
+    #[account(mut)]
+    pub migrator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Migrates a batch of accounts passed as (account, `AccountVersion` PDA) pairs through
+/// `remaining_accounts`. Accounts already at `to_version` are skipped, and the batch stops
+/// early once the remaining compute budget drops below `MIGRATION_COMPUTE_RESERVE`.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, MigrateAccounts<'info>>,
+    to_version: u8,
+) -> Result<()> {
+    validate_not_paused(&ctx.accounts.multisig_config, PAUSE_MIGRATION)?;
+    require!(
+        to_version > INITIAL_ACCOUNT_VERSION,
+        ErrorCode::InvalidAccountVersion
+    );
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.chunks_exact(2).remainder().is_empty(),
+        ErrorCode::InvalidMigrationBatch
+    );
+    
+    let clock = Clock::get()?;
+    let target_program = ctx.accounts.target_program.to_account_info();
+    let migrator = ctx.accounts.migrator.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let writer = MigrationWriter {
+        multisig_config: &ctx.accounts.multisig_config,
+        target_program: &target_program,
+        migrator: &migrator,
+        system_program: &system_program,
+    };
+    
+    let mut migrated: u32 = 0;
+    let mut skipped: u32 = 0;
+    let mut processed = 0;
+    
+    for accounts in ctx.remaining_accounts.chunks(2) {
+        // Leave the rest for the next batch rather than failing it part-way through
+        if sol_remaining_compute_units() < MIGRATION_COMPUTE_RESERVE {
+            break;
+        }
+        processed += 1;
+        
+        let (old_account, version_info) = (&accounts[0], &accounts[1]);
+        require!(
+            *old_account.owner == target_program.key(),
+            ErrorCode::InvalidTargetProgram
+        );
+        
+        let (version_address, version_bump) = Pubkey::find_program_address(
+            &[SEED_MIGRATION, old_account.key.as_ref()],
+            &crate::ID,
+        );
+        require!(
+            version_info.key() == version_address,
+            ErrorCode::InvalidAccountVersion
+        );
+        
+        // Records are only created once an account is actually migrated
+        let existing = if *version_info.owner == crate::ID {
+            Some(AccountVersion::try_deserialize(&mut &version_info.try_borrow_data()?[..])?)
+        } else {
+            None
+        };
+        let from_version = existing.as_ref().map_or(INITIAL_ACCOUNT_VERSION, |record| record.version);
+        
+        if from_version >= to_version {
+            skipped += 1;
+            continue;
+        }
+        
+        // Checks the migrator before anything is written
+        let (old_data, new_data) = writer.migrate(old_account, from_version, to_version)?;
+        
+        let mut account_version = match existing {
+            Some(record) => record,
+            None => {
+                create_version_record(version_info, old_account.key, version_bump, &migrator, &system_program)?;
+                AccountVersion {
+                    account: old_account.key(),
+                    version: INITIAL_ACCOUNT_VERSION,
+                    migrated: false,
+                    migrated_at: None,
+                    old_data_hash: [0; 32],
+                    new_data_hash: [0; 32],
+                }
+            }
+        };
+        
+        let tracker = &mut ctx.accounts.migration_tracker;
+        require!(
+            tracker.migrated_accounts < tracker.total_accounts,
+            ErrorCode::MigrationCountExceeded
+        );
+        tracker.migrated_accounts += 1;
+        
+        record_migration(
+            &mut account_version,
+            old_account.key(),
+            to_version,
+            clock.unix_timestamp,
+            &old_data,
+            &new_data,
+        );
+        account_version.try_serialize(&mut &mut version_info.try_borrow_mut_data()?[..])?;
+        migrated += 1;
+    }
+    
+    let tracker = &ctx.accounts.migration_tracker;
+    emit!(BatchMigratedEvent {
+        proposal_id: tracker.proposal_id,
+        to_version,
+        migrated,
+        skipped,
+        unprocessed: (ctx.remaining_accounts.len() / 2 - processed) as u32,
+        migrated_accounts: tracker.migrated_accounts,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Creates the `AccountVersion` PDA for `account`, paid by the migrator. Lamports already
+/// sent to the address are kept, so pre-funding it cannot block the migration.
+fn create_version_record<'info>(
+    version_info: &AccountInfo<'info>,
+    account: &Pubkey,
+    bump: u8,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let version_seeds = &[SEED_MIGRATION, account.as_ref(), &[bump]];
+    let required = Rent::get()?.minimum_balance(AccountVersion::LEN);
+    
+    if version_info.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: version_info.clone(),
+                },
+                &[version_seeds],
+            ),
+            required,
+            AccountVersion::LEN as u64,
+            &crate::ID,
+        );
+    }
+    
+    let shortfall = required.saturating_sub(version_info.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: version_info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate {
+                account_to_allocate: version_info.clone(),
+            },
+            &[version_seeds],
+        ),
+        AccountVersion::LEN as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Assign {
+                account_to_assign: version_info.clone(),
+            },
+            &[version_seeds],
+        ),
+        &crate::ID,
+    )
+}
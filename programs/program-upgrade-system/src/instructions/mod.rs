@@ -1,11 +1,30 @@
+#![allow(ambiguous_glob_reexports)]
+
 pub mod initialize_multisig;
 pub mod propose_upgrade;
 pub mod approve_upgrade;
 pub mod execute_upgrade;
 pub mod cancel_upgrade;
 pub mod migrate_account;
+pub mod migrate_accounts;
 pub mod pause_system;
 pub mod resume_system;
+pub mod ratify_pause;
+pub mod propose_action;
+pub mod register_program;
+pub mod deregister_program;
+pub mod execute_config_change;
+pub mod expire_proposal;
+pub mod revoke_approval;
+pub mod reject_upgrade;
+pub mod close_proposal;
+pub mod execute_authority_transfer;
+pub mod finalize_program;
+pub mod propose_instructions;
+pub mod execute_instructions;
+pub mod verify_account_version;
+pub mod start_migration;
+pub mod finalize_migration;
 
 pub use initialize_multisig::*;
 pub use propose_upgrade::*;
@@ -13,5 +32,22 @@ pub use approve_upgrade::*;
 pub use execute_upgrade::*;
 pub use cancel_upgrade::*;
 pub use migrate_account::*;
+pub use migrate_accounts::*;
 pub use pause_system::*;
 pub use resume_system::*;
+pub use ratify_pause::*;
+pub use propose_action::*;
+pub use register_program::*;
+pub use deregister_program::*;
+pub use execute_config_change::*;
+pub use expire_proposal::*;
+pub use revoke_approval::*;
+pub use reject_upgrade::*;
+pub use close_proposal::*;
+pub use execute_authority_transfer::*;
+pub use finalize_program::*;
+pub use propose_instructions::*;
+pub use execute_instructions::*;
+pub use verify_account_version::*;
+pub use start_migration::*;
+pub use finalize_migration::*;
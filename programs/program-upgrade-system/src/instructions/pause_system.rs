@@ -1,15 +1,16 @@
 use anchor_lang::prelude::*;
-use crate::state::MultisigConfig;
+use crate::state::{MultisigConfig, PauseRecord};
 use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
 
 #[derive(Accounts)]
 pub struct PauseSystem<'info> {
     #[account(
         mut,
-        seeds = [b"multisig"],
+        seeds = [SEED_MULTISIG],
         bump = multisig_config.bump,
-        constraint = multisig_config.members.contains(&pauser.key()) @ ErrorCode::NotAMember,
-        constraint = !multisig_config.is_paused @ ErrorCode::SystemAlreadyPaused,
     )]
     pub multisig_config: Account<'info, MultisigConfig>,
     
@@ -17,11 +18,82 @@ pub struct PauseSystem<'info> {
     pub pauser: Signer<'info>,
 }
 
-pub fn handler(ctx: Context<PauseSystem>) -> Result<()> {
-    let multisig = &mut ctx.accounts.multisig_config;
-    multisig.is_paused = true;
+pub fn handler(ctx: Context<PauseSystem>, scopes: u8, reason: String) -> Result<()> {
+    validate_member_role(&ctx.accounts.multisig_config, &ctx.accounts.pauser.key(), ROLE_PAUSER)?;
+    validate_pause_scopes(scopes)?;
+    require!(
+        reason.len() <= MAX_PAUSE_REASON_LENGTH,
+        ErrorCode::PauseReasonTooLong
+    );
     
-    msg!("System paused by: {}", ctx.accounts.pauser.key());
+    let pauser = ctx.accounts.pauser.key();
+    let clock = Clock::get()?;
+    let multisig: &mut MultisigConfig = &mut ctx.accounts.multisig_config;
+    
+    // Clear out a previous pause, or scopes added to it, that lapsed without being ratified
+    if active_paused_scopes(multisig, clock.unix_timestamp) == 0 {
+        multisig.paused_scopes = 0;
+        multisig.pause = None;
+    }
+    if let Some(record) = multisig.pause.as_mut().filter(|record| record.pending_lapsed(clock.unix_timestamp)) {
+        multisig.paused_scopes &= !record.pending_scopes;
+        record.pending_scopes = 0;
+        record.pending_expires_at = None;
+    }
+    
+    require!(
+        multisig.paused_scopes & scopes != scopes,
+        ErrorCode::SystemAlreadyPaused
+    );
+    
+    let new_scopes = scopes & !multisig.paused_scopes;
+    multisig.paused_scopes |= scopes;
+    
+    let expires_at = clock.unix_timestamp
+        .checked_add(multisig.pause_window)
+        .ok_or(ErrorCode::MathOverflow)?;
+    // A pauser who alone meets the threshold ratifies immediately
+    let ratified = tally_member_weight(&multisig.members, &[pauser]) >= multisig.threshold;
+    
+    let scopes_expire_at = match multisig.pause.as_mut() {
+        None => {
+            multisig.pause = Some(PauseRecord {
+                paused_by: pauser,
+                reason: reason.clone(),
+                paused_at: clock.unix_timestamp,
+                expires_at: if ratified { None } else { Some(expires_at) },
+                ratifications: vec![pauser],
+                resume_votes: vec![],
+                pending_scopes: 0,
+                pending_expires_at: None,
+            });
+            if ratified { None } else { Some(expires_at) }
+        }
+        // Additional scopes join an unratified pause and share its expiry
+        Some(record) if !record.is_ratified() => record.expires_at,
+        Some(_) if ratified => None,
+        // Scopes added to a ratified pause need their own ratification, and lapse
+        // without it
+        Some(record) => {
+            if record.pending_scopes == 0 {
+                record.pending_expires_at = Some(expires_at);
+                record.ratifications = vec![pauser];
+            } else if !record.ratifications.contains(&pauser) {
+                record.ratifications.push(pauser);
+            }
+            record.pending_scopes |= new_scopes;
+            record.pending_expires_at
+        }
+    };
+    
+    emit!(SystemPausedEvent {
+        pauser,
+        scopes,
+        paused_scopes: multisig.paused_scopes,
+        reason,
+        expires_at: scopes_expire_at,
+        timestamp: clock.unix_timestamp,
+    });
     
     Ok(())
 }
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct ProposeAction<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_MULTISIG],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Box<Account<'info, MultisigConfig>>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = UpgradeProposal::LEN,
+        seeds = [SEED_PROPOSAL, multisig_config.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, UpgradeProposal>>,
+    
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<ProposeAction>,
+    action: ProposalAction,
+    target_program: Pubkey,
+    description: String,
+    timelock_period: Option<i64>,
+) -> Result<()> {
+    validate_description_length(&description, MAX_DESCRIPTION_LENGTH)?;
+    validate_member_role(&ctx.accounts.multisig_config, &ctx.accounts.proposer.key(), ROLE_PROPOSER)?;
+    validate_not_paused(&ctx.accounts.multisig_config, PAUSE_PROPOSALS)?;
+    
+    // Upgrades carry a buffer and go through propose_upgrade; instruction lists go
+    // through propose_instructions
+    let target_program = match action {
+        ProposalAction::Upgrade | ProposalAction::ExecuteInstructions => {
+            return err!(ErrorCode::InvalidProposalAction)
+        }
+        ProposalAction::RegisterProgram
+        | ProposalAction::DeregisterProgram
+        | ProposalAction::SetUpgradeAuthority { .. }
+        | ProposalAction::SetUpgradeAuthorityChecked { .. }
+        | ProposalAction::FinalizeProgram => {
+            require!(
+                target_program != Pubkey::default(),
+                ErrorCode::InvalidTargetProgram
+            );
+            target_program
+        }
+        _ => {
+            validate_config_change(&ctx.accounts.multisig_config, &action)?;
+            Pubkey::default()
+        }
+    };
+    
+    let mut timelock_period = resolve_timelock_period(&ctx.accounts.multisig_config, timelock_period)?;
+    if action == ProposalAction::FinalizeProgram {
+        timelock_period = resolve_finalize_timelock(timelock_period);
+    }
+    
+    let clock = Clock::get()?;
+    let multisig: &mut MultisigConfig = &mut ctx.accounts.multisig_config;
+    let proposal = &mut ctx.accounts.proposal;
+    
+    UpgradeProposal::init(
+        proposal,
+        multisig,
+        ctx.accounts.proposer.key(),
+        action,
+        description.clone(),
+        timelock_period,
+        ctx.bumps.proposal,
+    )?;
+    proposal.target_program = target_program;
+    
+    emit!(ProposalCreatedEvent {
+        proposal_id: proposal.id,
+        index: proposal.index,
+        proposer: proposal.proposer,
+        action,
+        target_program,
+        new_program_buffer: Pubkey::default(),
+        buffer_hash: [0; 32],
+        bundled_upgrades: vec![],
+        description,
+        timelock_end: clock.unix_timestamp
+            .checked_add(timelock_period)
+            .ok_or(ErrorCode::MathOverflow)?,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
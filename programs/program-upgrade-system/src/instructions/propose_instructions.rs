@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+#[instruction(instructions: Vec<GovernedInstruction>)]
+pub struct ProposeInstructions<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_MULTISIG],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Box<Account<'info, MultisigConfig>>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = UpgradeProposal::LEN,
+        seeds = [SEED_PROPOSAL, multisig_config.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, UpgradeProposal>>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = ProposalInstructions::space(&instructions),
+        seeds = [SEED_INSTRUCTIONS, proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_instructions: Box<Account<'info, ProposalInstructions>>,
+    
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<ProposeInstructions>,
+    instructions: Vec<GovernedInstruction>,
+    description: String,
+    timelock_period: Option<i64>,
+) -> Result<()> {
+    validate_description_length(&description, MAX_DESCRIPTION_LENGTH)?;
+    validate_member_role(&ctx.accounts.multisig_config, &ctx.accounts.proposer.key(), ROLE_PROPOSER)?;
+    validate_not_paused(&ctx.accounts.multisig_config, PAUSE_PROPOSALS)?;
+    validate_governed_instructions(&instructions)?;
+    
+    let timelock_period = resolve_timelock_period(&ctx.accounts.multisig_config, timelock_period)?;
+    
+    let clock = Clock::get()?;
+    let multisig: &mut MultisigConfig = &mut ctx.accounts.multisig_config;
+    let proposal = &mut ctx.accounts.proposal;
+    
+    UpgradeProposal::init(
+        proposal,
+        multisig,
+        ctx.accounts.proposer.key(),
+        ProposalAction::ExecuteInstructions,
+        description.clone(),
+        timelock_period,
+        ctx.bumps.proposal,
+    )?;
+    
+    let proposal_instructions = &mut ctx.accounts.proposal_instructions;
+    proposal_instructions.proposal = proposal.key();
+    proposal_instructions.instructions = instructions;
+    proposal_instructions.bump = ctx.bumps.proposal_instructions;
+    
+    emit!(ProposalCreatedEvent {
+        proposal_id: proposal.id,
+        index: proposal.index,
+        proposer: proposal.proposer,
+        action: ProposalAction::ExecuteInstructions,
+        target_program: Pubkey::default(),
+        new_program_buffer: Pubkey::default(),
+        buffer_hash: [0; 32],
+        bundled_upgrades: vec![],
+        description,
+        timelock_end: clock.unix_timestamp
+            .checked_add(timelock_period)
+            .ok_or(ErrorCode::MathOverflow)?,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
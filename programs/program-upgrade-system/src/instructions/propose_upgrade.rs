@@ -5,23 +5,37 @@ use crate::events::*;
 use crate::constants::*;
 use crate::utils::*;
 
+/// Extra programs upgraded in the same bundle pass `[buffer, managed_program]` for each
+/// entry of `bundled_upgrades`, in order, through `remaining_accounts`.
 #[derive(Accounts)]
-#[instruction(new_program_buffer: Pubkey, description: String)]
+#[instruction(new_program_buffer: Pubkey, target_program: Pubkey)]
 pub struct ProposeUpgrade<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_MULTISIG],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Box<Account<'info, MultisigConfig>>,
+    
     #[account(
         init,
         payer = proposer,
         space = UpgradeProposal::LEN,
-        seeds = [SEED_PROPOSAL, new_program_buffer.as_ref()],
+        seeds = [SEED_PROPOSAL, multisig_config.proposal_count.to_le_bytes().as_ref()],
         bump
     )]
     pub proposal: Box<Account<'info, UpgradeProposal>>,
     
+    /// CHECK: Validated in the handler as a loader buffer locked to the multisig
+    #[account(address = new_program_buffer @ ErrorCode::InvalidProgramBuffer)]
+    pub buffer: UncheckedAccount<'info>,
+    
+    /// CHECK: Registry entry for the target; may not exist yet, checked for finalization
     #[account(
-        seeds = [SEED_MULTISIG],
-        bump = multisig_config.bump,
+        seeds = [SEED_MANAGED_PROGRAM, target_program.as_ref()],
+        bump,
     )]
-    pub multisig_config: Box<Account<'info, MultisigConfig>>,
+    pub managed_program: UncheckedAccount<'info>,
     
     #[account(mut)]
     pub proposer: Signer<'info>,
@@ -29,39 +43,117 @@ pub struct ProposeUpgrade<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
-    ctx: Context<ProposeUpgrade>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ProposeUpgrade<'info>>,
     new_program_buffer: Pubkey,
+    target_program: Pubkey,
     description: String,
+    timelock_period: Option<i64>,
+    emergency: bool,
+    bundled_upgrades: Vec<UpgradeTarget>,
 ) -> Result<()> {
     validate_description_length(&description, MAX_DESCRIPTION_LENGTH)?;
-    validate_multisig_member(&ctx.accounts.multisig_config.members, &ctx.accounts.proposer.key())?;
+    require!(
+        target_program != Pubkey::default(),
+        ErrorCode::InvalidTargetProgram
+    );
+    validate_member_role(&ctx.accounts.multisig_config, &ctx.accounts.proposer.key(), ROLE_PROPOSER)?;
+    validate_not_paused(&ctx.accounts.multisig_config, PAUSE_PROPOSALS)?;
+    validate_not_finalized(&ctx.accounts.managed_program)?;
+    
+    validate_upgrade_bundle(&target_program, &new_program_buffer, &bundled_upgrades)?;
+    require!(
+        ctx.remaining_accounts.len() == bundled_upgrades.len() * 2,
+        ErrorCode::InvalidBundleAccounts
+    );
+    validate_bundle_size(
+        std::iter::once(ctx.accounts.buffer.as_ref())
+            .chain(ctx.remaining_accounts.iter().step_by(2)),
+    )?;
+    
+    // Lock in the buffer contents that members are approving
+    let (buffer_hash, buffer_len) = read_locked_buffer(
+        &ctx.accounts.buffer,
+        &ctx.accounts.multisig_config.key(),
+    )?;
+    
+    let mut bundle = Vec::with_capacity(bundled_upgrades.len());
+    for (upgrade, accounts) in bundled_upgrades.iter().zip(ctx.remaining_accounts.chunks(2)) {
+        let (buffer, managed_program) = (&accounts[0], &accounts[1]);
+        require!(
+            buffer.key() == upgrade.buffer,
+            ErrorCode::InvalidProgramBuffer
+        );
+        require!(
+            managed_program.key() == get_managed_program_address(&upgrade.target_program),
+            ErrorCode::InvalidBundleAccounts
+        );
+        validate_not_finalized(managed_program)?;
+        
+        let (buffer_hash, buffer_len) = read_locked_buffer(
+            buffer,
+            &ctx.accounts.multisig_config.key(),
+        )?;
+        bundle.push(BundledUpgrade {
+            target_program: upgrade.target_program,
+            buffer: upgrade.buffer,
+            buffer_hash,
+            buffer_len,
+        });
+    }
+    
+    let timelock_period = if emergency {
+        resolve_emergency_timelock_period(&ctx.accounts.multisig_config, timelock_period)?
+    } else {
+        resolve_timelock_period(&ctx.accounts.multisig_config, timelock_period)?
+    };
     
     let clock = Clock::get()?;
+    let multisig: &mut MultisigConfig = &mut ctx.accounts.multisig_config;
     let proposal = &mut ctx.accounts.proposal;
     
-    proposal.id = proposal.key();
-    proposal.proposer = ctx.accounts.proposer.key();
+    UpgradeProposal::init(
+        proposal,
+        multisig,
+        ctx.accounts.proposer.key(),
+        ProposalAction::Upgrade,
+        description.clone(),
+        timelock_period,
+        ctx.bumps.proposal,
+    )?;
     proposal.new_program_buffer = new_program_buffer;
-    proposal.target_program = crate::ID;
-    proposal.description = description.clone();
-    proposal.status = UpgradeStatus::Proposed;
-    proposal.approvals = vec![];
-    proposal.approval_count = 0;
-    proposal.created_at = clock.unix_timestamp;
-    proposal.timelock_activated_at = None;
-    proposal.timelock_period = TIMELOCK_PERIOD;
-    proposal.executed_at = None;
-    proposal.bump = ctx.bumps.proposal;
+    proposal.buffer_hash = buffer_hash;
+    proposal.buffer_len = buffer_len;
+    proposal.target_program = target_program;
+    proposal.bundled_upgrades = bundle.clone();
+    proposal.emergency = emergency;
     
     emit!(ProposalCreatedEvent {
         proposal_id: proposal.id,
+        index: proposal.index,
         proposer: proposal.proposer,
+        action: ProposalAction::Upgrade,
+        target_program,
         new_program_buffer,
+        buffer_hash,
+        bundled_upgrades: bundle,
         description,
-        timelock_end: clock.unix_timestamp + TIMELOCK_PERIOD,
+        timelock_end: clock.unix_timestamp
+            .checked_add(timelock_period)
+            .ok_or(ErrorCode::MathOverflow)?,
         timestamp: clock.unix_timestamp,
     });
     
+    if emergency {
+        emit!(EmergencyProposalEvent {
+            proposal_id: proposal.id,
+            proposer: proposal.proposer,
+            target_program,
+            emergency_threshold: required_threshold(proposal, multisig),
+            timelock_period,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    
     Ok(())
 }
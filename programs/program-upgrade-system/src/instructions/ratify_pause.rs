@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::MultisigConfig;
+use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct RatifyPause<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_MULTISIG],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Account<'info, MultisigConfig>,
+    
+    pub ratifier: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RatifyPause>) -> Result<()> {
+    let ratifier = ctx.accounts.ratifier.key();
+    validate_member_role(&ctx.accounts.multisig_config, &ratifier, ROLE_APPROVER)?;
+    let clock = Clock::get()?;
+    let multisig: &mut MultisigConfig = &mut ctx.accounts.multisig_config;
+    
+    require!(
+        active_paused_scopes(multisig, clock.unix_timestamp) != 0,
+        ErrorCode::SystemNotPaused
+    );
+    
+    let record = multisig.pause.as_mut().ok_or(ErrorCode::SystemNotPaused)?;
+    
+    // Once the pause is ratified, only scopes added to it afterwards await ratification
+    let ratifying_pending = record.is_ratified();
+    require!(
+        !ratifying_pending
+            || (record.pending_scopes != 0 && !record.pending_lapsed(clock.unix_timestamp)),
+        ErrorCode::PauseAlreadyRatified
+    );
+    require!(
+        !record.ratifications.contains(&ratifier),
+        ErrorCode::DuplicateRatification
+    );
+    
+    record.ratifications.push(ratifier);
+    
+    let ratification_weight = tally_member_weight(&multisig.members, &record.ratifications);
+    let ratified = ratification_weight >= multisig.threshold;
+    
+    // A ratified pause no longer lapses
+    if ratified && ratifying_pending {
+        record.pending_scopes = 0;
+        record.pending_expires_at = None;
+    } else if ratified {
+        record.expires_at = None;
+    }
+    
+    emit!(PauseRatifiedEvent {
+        ratifier,
+        ratification_weight,
+        threshold: multisig.threshold,
+        ratified,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
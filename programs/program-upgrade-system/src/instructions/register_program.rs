@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct RegisterProgram<'info> {
+    #[account(
+        mut,
+        constraint = proposal.status == UpgradeStatus::TimelockActive @ ErrorCode::InvalidProposalState,
+        constraint = proposal.action == ProposalAction::RegisterProgram @ ErrorCode::InvalidProposalAction,
+    )]
+    pub proposal: Box<Account<'info, UpgradeProposal>>,
+    
+    #[account(
+        seeds = [SEED_MULTISIG],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Box<Account<'info, MultisigConfig>>,
+    
+    #[account(
+        init,
+        payer = executor,
+        space = ManagedProgram::LEN,
+        seeds = [SEED_MANAGED_PROGRAM, proposal.target_program.as_ref()],
+        bump
+    )]
+    pub managed_program: Box<Account<'info, ManagedProgram>>,
+    
+    /// CHECK: The program being placed under multisig governance
+    #[account(address = proposal.target_program @ ErrorCode::InvalidTargetProgram)]
+    pub program: UncheckedAccount<'info>,
+    
+    /// CHECK: Program data account derived from the program id
+    #[account(address = get_program_data_address(&proposal.target_program) @ ErrorCode::InvalidProgramData)]
+    pub program_data: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterProgram>) -> Result<()> {
+    validate_member_role(&ctx.accounts.multisig_config, &ctx.accounts.executor.key(), ROLE_EXECUTOR)?;
+    
+    validate_upgradeable_program(&ctx.accounts.program.to_account_info())?;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    validate_ready_for_execution(proposal, &ctx.accounts.multisig_config)?;
+    
+    let clock = Clock::get()?;
+    
+    let managed_program = &mut ctx.accounts.managed_program;
+    managed_program.program_id = ctx.accounts.program.key();
+    managed_program.program_data = ctx.accounts.program_data.key();
+    managed_program.registered_at = clock.unix_timestamp;
+    managed_program.finalized = false;
+    managed_program.bump = ctx.bumps.managed_program;
+    
+    proposal.status = UpgradeStatus::Executed;
+    proposal.executed_at = Some(clock.unix_timestamp);
+    proposal.resolved_at = Some(clock.unix_timestamp);
+    
+    emit!(ProgramRegisteredEvent {
+        proposal_id: proposal.id,
+        program_id: managed_program.program_id,
+        program_data: managed_program.program_data,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct RejectUpgrade<'info> {
+    #[account(
+        mut,
+        constraint = proposal.status == UpgradeStatus::Proposed
+            || proposal.status == UpgradeStatus::Approved
+            @ ErrorCode::InvalidProposalState,
+    )]
+    pub proposal: Box<Account<'info, UpgradeProposal>>,
+    
+    #[account(
+        seeds = [SEED_MULTISIG],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Box<Account<'info, MultisigConfig>>,
+    
+    pub rejector: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<RejectUpgrade>,
+    _proposal_id: Pubkey,
+) -> Result<()> {
+    let multisig = &ctx.accounts.multisig_config;
+    validate_member_role(multisig, &ctx.accounts.rejector.key(), ROLE_APPROVER)?;
+    validate_not_paused(multisig, PAUSE_APPROVALS)?;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    validate_not_expired(proposal)?;
+    
+    let rejector = ctx.accounts.rejector.key();
+    
+    // Check for duplicate or conflicting vote
+    require!(
+        !proposal.rejections.contains(&rejector),
+        ErrorCode::DuplicateRejection
+    );
+    require!(
+        !proposal.approvals.contains(&rejector),
+        ErrorCode::ConflictingVote
+    );
+    
+    // Add rejection
+    proposal.rejections.push(rejector);
+    proposal.rejection_count += 1;
+    
+    let clock = Clock::get()?;
+    let proposal_rejected = !validate_threshold_reachable(proposal, multisig)?;
+    
+    if proposal_rejected {
+        proposal.status = UpgradeStatus::Rejected;
+        proposal.resolved_at = Some(clock.unix_timestamp);
+    }
+    
+    emit!(RejectionEvent {
+        proposal_id: proposal.id,
+        rejector,
+        rejection_weight: tally_member_weight(&multisig.members, &proposal.rejections),
+        threshold: required_threshold(proposal, multisig),
+        proposal_rejected,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
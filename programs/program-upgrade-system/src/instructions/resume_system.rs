@@ -1,15 +1,16 @@
 use anchor_lang::prelude::*;
 use crate::state::MultisigConfig;
 use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
 
 #[derive(Accounts)]
 pub struct ResumeSystem<'info> {
     #[account(
         mut,
-        seeds = [b"multisig"],
+        seeds = [SEED_MULTISIG],
         bump = multisig_config.bump,
-        constraint = multisig_config.members.contains(&resumer.key()) @ ErrorCode::NotAMember,
-        constraint = multisig_config.is_paused @ ErrorCode::SystemNotPaused,
     )]
     pub multisig_config: Account<'info, MultisigConfig>,
     
@@ -17,11 +18,66 @@ pub struct ResumeSystem<'info> {
     pub resumer: Signer<'info>,
 }
 
-pub fn handler(ctx: Context<ResumeSystem>) -> Result<()> {
-    let multisig = &mut ctx.accounts.multisig_config;
-    multisig.is_paused = false;
+pub fn handler(ctx: Context<ResumeSystem>, scopes: u8) -> Result<()> {
+    validate_pause_scopes(scopes)?;
     
-    msg!("System resumed by: {}", ctx.accounts.resumer.key());
+    let resumer = ctx.accounts.resumer.key();
+    let clock = Clock::get()?;
+    let multisig: &mut MultisigConfig = &mut ctx.accounts.multisig_config;
+    
+    require!(
+        active_paused_scopes(multisig, clock.unix_timestamp) & scopes != 0,
+        ErrorCode::SystemNotPaused
+    );
+    
+    // Lifting a ratified pause needs threshold approver votes, and lifts it in full.
+    // Scopes still awaiting ratification can be lifted on their own like an unratified pause.
+    let ratified = multisig.pause.as_ref().is_some_and(|record| {
+        record.is_ratified() && scopes & !record.pending_scopes != 0
+    });
+    let required_role = if ratified { ROLE_APPROVER } else { ROLE_PAUSER };
+    validate_member_role(multisig, &resumer, required_role)?;
+    
+    let mut resumed_scopes = scopes;
+    if let Some(record) = multisig.pause.as_mut().filter(|_| ratified) {
+        require!(
+            !record.resume_votes.contains(&resumer),
+            ErrorCode::DuplicateResumeVote
+        );
+        record.resume_votes.push(resumer);
+        
+        let vote_weight = tally_member_weight(&multisig.members, &record.resume_votes);
+        
+        emit!(ResumeVoteEvent {
+            voter: resumer,
+            vote_weight,
+            threshold: multisig.threshold,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        if vote_weight < multisig.threshold {
+            return Ok(());
+        }
+        
+        resumed_scopes = multisig.paused_scopes;
+    }
+    
+    multisig.paused_scopes &= !resumed_scopes;
+    if multisig.paused_scopes == 0 {
+        multisig.pause = None;
+    } else if let Some(record) = multisig.pause.as_mut() {
+        record.pending_scopes &= multisig.paused_scopes;
+        if record.pending_scopes == 0 {
+            record.pending_expires_at = None;
+        }
+    }
+    
+    emit!(SystemResumedEvent {
+        resumer,
+        scopes: resumed_scopes,
+        paused_scopes: multisig.paused_scopes,
+        timestamp: clock.unix_timestamp,
+    });
     
     Ok(())
 }
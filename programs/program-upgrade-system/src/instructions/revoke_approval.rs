@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct RevokeApproval<'info> {
+    #[account(
+        mut,
+        constraint = proposal.status == UpgradeStatus::Proposed
+            || proposal.status == UpgradeStatus::Approved
+            || proposal.status == UpgradeStatus::TimelockActive
+            @ ErrorCode::InvalidProposalState,
+    )]
+    pub proposal: Box<Account<'info, UpgradeProposal>>,
+    
+    #[account(
+        seeds = [SEED_MULTISIG],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Box<Account<'info, MultisigConfig>>,
+    
+    pub approver: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<RevokeApproval>,
+    _proposal_id: Pubkey,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    validate_not_expired(proposal)?;
+    
+    let approver = ctx.accounts.approver.key();
+    require!(
+        proposal.approvals.contains(&approver),
+        ErrorCode::ApprovalNotFound
+    );
+    
+    // Remove approval
+    proposal.approvals.retain(|a| *a != approver);
+    proposal.approval_count = proposal.approval_count
+        .checked_sub(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    
+    let clock = Clock::get()?;
+    let approval_weight = tally_member_weight(&ctx.accounts.multisig_config.members, &proposal.approvals);
+    let threshold = required_threshold(proposal, &ctx.accounts.multisig_config);
+    let threshold_met = validate_threshold(approval_weight, threshold)?;
+    
+    // Falling below threshold stops the timelock; it restarts when threshold is met again
+    let mut timelock_reset = false;
+    if !threshold_met && proposal.status != UpgradeStatus::Proposed {
+        timelock_reset = proposal.timelock_activated_at.is_some();
+        proposal.status = UpgradeStatus::Proposed;
+        proposal.timelock_activated_at = None;
+    }
+    
+    emit!(ApprovalRevokedEvent {
+        proposal_id: proposal.id,
+        approver,
+        approval_weight,
+        threshold,
+        timelock_reset,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct StartMigration<'info> {
+    #[account(
+        constraint = proposal.status == UpgradeStatus::Executed @ ErrorCode::InvalidProposalState,
+        constraint = proposal.action == ProposalAction::Upgrade @ ErrorCode::InvalidProposalAction,
+    )]
+    pub proposal: Box<Account<'info, UpgradeProposal>>,
+    
+    #[account(
+        init,
+        payer = migrator,
+        space = MigrationTracker::LEN,
+        seeds = [SEED_MIGRATION_TRACKER, proposal.key().as_ref()],
+        bump
+    )]
+    pub migration_tracker: Account<'info, MigrationTracker>,
+    
+    #[account(
+        seeds = [SEED_MULTISIG],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Box<Account<'info, MultisigConfig>>,
+    
+    #[account(mut)]
+    pub migrator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<StartMigration>, total_accounts: u64) -> Result<()> {
+    validate_member_role(&ctx.accounts.multisig_config, &ctx.accounts.migrator.key(), ROLE_MIGRATOR)?;
+    validate_not_paused(&ctx.accounts.multisig_config, PAUSE_MIGRATION)?;
+    require!(total_accounts > 0, ErrorCode::InvalidMigrationCount);
+    
+    let clock = Clock::get()?;
+    
+    let tracker = &mut ctx.accounts.migration_tracker;
+    tracker.proposal_id = ctx.accounts.proposal.key();
+    tracker.total_accounts = total_accounts;
+    tracker.migrated_accounts = 0;
+    tracker.started_at = clock.unix_timestamp;
+    tracker.completed_at = None;
+    tracker.bump = ctx.bumps.migration_tracker;
+    
+    emit!(MigrationStartedEvent {
+        proposal_id: tracker.proposal_id,
+        tracker: tracker.key(),
+        total_accounts,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
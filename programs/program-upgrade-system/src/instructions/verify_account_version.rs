@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::*;
+use crate::constants::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct VerifyAccountVersion<'info> {
+    #[account(
+        seeds = [SEED_MIGRATION, account.key().as_ref()],
+        bump,
+        constraint = account_version.migrated @ ErrorCode::AccountNotMigrated,
+    )]
+    pub account_version: Account<'info, AccountVersion>,
+    
+    /// CHECK: The migrated account; only its data is read
+    pub account: UncheckedAccount<'info>,
+}
+
+/// Recomputes the hash of the account's current data and checks it against the hash
+/// recorded at migration. Read-only, so anyone can call or simulate it.
+pub fn handler(ctx: Context<VerifyAccountVersion>) -> Result<()> {
+    let account_version = &ctx.accounts.account_version;
+    let data_hash = hash_account_data(&ctx.accounts.account.try_borrow_data()?);
+    
+    require!(
+        data_hash == account_version.new_data_hash,
+        ErrorCode::AccountDataMismatch
+    );
+    
+    let clock = Clock::get()?;
+    
+    emit!(AccountVerifiedEvent {
+        account: ctx.accounts.account.key(),
+        version: account_version.version,
+        data_hash,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
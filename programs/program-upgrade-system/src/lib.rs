@@ -4,6 +4,7 @@ pub mod instructions;
 pub mod state;
 pub mod events;
 pub mod utils;
+pub mod migrations;
 
 use anchor_lang::prelude::*;
 
@@ -20,18 +21,42 @@ pub mod program_upgrade_system {
 
     pub fn initialize_multisig(
         ctx: Context<InitializeMultisig>,
-        members: Vec<Pubkey>,
-        threshold: u8,
+        members: Vec<Member>,
+        threshold: u16,
+        min_timelock: i64,
+        default_timelock: i64,
+        approval_window: i64,
+        execution_window: i64,
     ) -> Result<()> {
-        instructions::initialize_multisig::handler(ctx, members, threshold)
+        instructions::initialize_multisig::handler(
+            ctx,
+            members,
+            threshold,
+            min_timelock,
+            default_timelock,
+            approval_window,
+            execution_window,
+        )
     }
 
-    pub fn propose_upgrade(
-        ctx: Context<ProposeUpgrade>,
+    pub fn propose_upgrade<'info>(
+        ctx: Context<'_, '_, '_, 'info, ProposeUpgrade<'info>>,
         new_program_buffer: Pubkey,
+        target_program: Pubkey,
         description: String,
+        timelock_period: Option<i64>,
+        emergency: bool,
+        bundled_upgrades: Vec<UpgradeTarget>,
     ) -> Result<()> {
-        instructions::propose_upgrade::handler(ctx, new_program_buffer, description)
+        instructions::propose_upgrade::handler(
+            ctx,
+            new_program_buffer,
+            target_program,
+            description,
+            timelock_period,
+            emergency,
+            bundled_upgrades,
+        )
     }
 
     pub fn approve_upgrade(
@@ -41,32 +66,131 @@ pub mod program_upgrade_system {
         instructions::approve_upgrade::handler(ctx, proposal_id)
     }
 
-    pub fn execute_upgrade(
-        ctx: Context<ExecuteUpgrade>,
+    pub fn reject_upgrade(
+        ctx: Context<RejectUpgrade>,
+        proposal_id: Pubkey,
+    ) -> Result<()> {
+        instructions::reject_upgrade::handler(ctx, proposal_id)
+    }
+
+    pub fn revoke_approval(
+        ctx: Context<RevokeApproval>,
+        proposal_id: Pubkey,
+    ) -> Result<()> {
+        instructions::revoke_approval::handler(ctx, proposal_id)
+    }
+
+    pub fn execute_upgrade<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteUpgrade<'info>>,
         proposal_id: Pubkey,
     ) -> Result<()> {
         instructions::execute_upgrade::handler(ctx, proposal_id)
     }
 
-    pub fn cancel_upgrade(
-        ctx: Context<CancelUpgrade>,
+    pub fn cancel_upgrade<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelUpgrade<'info>>,
         proposal_id: Pubkey,
+        reason: String,
+        close_proposal: bool,
     ) -> Result<()> {
-        instructions::cancel_upgrade::handler(ctx, proposal_id)
+        instructions::cancel_upgrade::handler(ctx, proposal_id, reason, close_proposal)
+    }
+
+    pub fn start_migration(ctx: Context<StartMigration>, total_accounts: u64) -> Result<()> {
+        instructions::start_migration::handler(ctx, total_accounts)
     }
 
     pub fn migrate_account(
         ctx: Context<MigrateAccount>,
         old_account: Pubkey,
+        to_version: u8,
+    ) -> Result<()> {
+        instructions::migrate_account::handler(ctx, old_account, to_version)
+    }
+
+    pub fn migrate_accounts<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateAccounts<'info>>,
+        to_version: u8,
+    ) -> Result<()> {
+        instructions::migrate_accounts::handler(ctx, to_version)
+    }
+
+    pub fn finalize_migration(ctx: Context<FinalizeMigration>) -> Result<()> {
+        instructions::finalize_migration::handler(ctx)
+    }
+
+    pub fn verify_account_version(ctx: Context<VerifyAccountVersion>) -> Result<()> {
+        instructions::verify_account_version::handler(ctx)
+    }
+
+    pub fn pause_system(
+        ctx: Context<PauseSystem>,
+        scopes: u8,
+        reason: String,
+    ) -> Result<()> {
+        instructions::pause_system::handler(ctx, scopes, reason)
+    }
+
+    pub fn ratify_pause(ctx: Context<RatifyPause>) -> Result<()> {
+        instructions::ratify_pause::handler(ctx)
+    }
+
+    pub fn resume_system(ctx: Context<ResumeSystem>, scopes: u8) -> Result<()> {
+        instructions::resume_system::handler(ctx, scopes)
+    }
+
+    pub fn propose_action(
+        ctx: Context<ProposeAction>,
+        action: ProposalAction,
+        target_program: Pubkey,
+        description: String,
+        timelock_period: Option<i64>,
     ) -> Result<()> {
-        instructions::migrate_account::handler(ctx, old_account)
+        instructions::propose_action::handler(ctx, action, target_program, description, timelock_period)
+    }
+
+    pub fn register_program(ctx: Context<RegisterProgram>) -> Result<()> {
+        instructions::register_program::handler(ctx)
+    }
+
+    pub fn deregister_program(ctx: Context<DeregisterProgram>) -> Result<()> {
+        instructions::deregister_program::handler(ctx)
     }
 
-    pub fn pause_system(ctx: Context<PauseSystem>) -> Result<()> {
-        instructions::pause_system::handler(ctx)
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        instructions::execute_config_change::handler(ctx)
     }
 
-    pub fn resume_system(ctx: Context<ResumeSystem>) -> Result<()> {
-        instructions::resume_system::handler(ctx)
+    pub fn expire_proposal(ctx: Context<ExpireProposal>) -> Result<()> {
+        instructions::expire_proposal::handler(ctx)
+    }
+
+    pub fn close_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseProposal<'info>>,
+    ) -> Result<()> {
+        instructions::close_proposal::handler(ctx)
+    }
+
+    pub fn execute_authority_transfer(ctx: Context<ExecuteAuthorityTransfer>) -> Result<()> {
+        instructions::execute_authority_transfer::handler(ctx)
+    }
+
+    pub fn finalize_program(ctx: Context<FinalizeProgram>) -> Result<()> {
+        instructions::finalize_program::handler(ctx)
+    }
+
+    pub fn propose_instructions(
+        ctx: Context<ProposeInstructions>,
+        instructions: Vec<GovernedInstruction>,
+        description: String,
+        timelock_period: Option<i64>,
+    ) -> Result<()> {
+        instructions::propose_instructions::handler(ctx, instructions, description, timelock_period)
+    }
+
+    pub fn execute_instructions<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteInstructions<'info>>,
+    ) -> Result<()> {
+        instructions::execute_instructions::handler(ctx)
     }
 }
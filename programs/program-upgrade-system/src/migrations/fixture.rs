@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use super::AccountMigration;
+
+/// `FixtureRecord` of the migration hook fixture program, as first created.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct FixtureRecordV1 {
+    pub owner: Pubkey,
+    pub value: u64,
+}
+
+/// `FixtureRecord` with a freeze flag.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct FixtureRecordV2 {
+    pub owner: Pubkey,
+    pub value: u64,
+    pub frozen: bool,
+}
+
+pub struct FixtureRecordV1ToV2;
+
+impl AccountMigration for FixtureRecordV1ToV2 {
+    const DISCRIMINATOR: [u8; 8] = [74, 48, 99, 157, 243, 214, 46, 227]; // sha256("account:FixtureRecord")[..8]
+    const FROM_VERSION: u8 = 1;
+    const TO_VERSION: u8 = 2;
+    const OLD_LEN: usize = 8 + 32 + 8;
+    
+    type Old = FixtureRecordV1;
+    type New = FixtureRecordV2;
+    
+    fn migrate(old: FixtureRecordV1) -> FixtureRecordV2 {
+        FixtureRecordV2 {
+            owner: old.owner,
+            value: old.value,
+            frozen: false,
+        }
+    }
+    
+    fn owner(old: &FixtureRecordV1) -> Option<Pubkey> {
+        Some(old.owner)
+    }
+}
@@ -0,0 +1,206 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use anchor_lang::system_program;
+use crate::state::MultisigConfig;
+use crate::utils::validate_migrator;
+use crate::error::ErrorCode;
+use crate::constants::*;
+
+#[cfg(feature = "test-fixtures")]
+pub mod fixture;
+
+/// A layout change for one account type of a governed program. The old layout is
+/// deserialized from the bytes after the 8-byte discriminator, transformed, and written
+/// back behind the same discriminator.
+pub trait AccountMigration {
+    const DISCRIMINATOR: [u8; 8];
+    const FROM_VERSION: u8;
+    const TO_VERSION: u8;
+    
+    /// Length of an account in the old layout, discriminator included. Accounts without a
+    /// version record count as `INITIAL_ACCOUNT_VERSION`, so this is what keeps an account
+    /// the upgraded program created in a later layout from being read as the old one.
+    const OLD_LEN: usize;
+    
+    type Old: AnchorDeserialize;
+    type New: AnchorSerialize;
+    
+    fn migrate(old: Self::Old) -> Self::New;
+    
+    /// The user who owns the account, if the old layout records one. The owner may
+    /// migrate the account without holding `ROLE_MIGRATOR`, but only when the new layout
+    /// is longer than `OLD_LEN`. Otherwise an account already in the new layout could pass
+    /// the length check and have fields its program controls reset by its owner.
+    fn owner(_old: &Self::Old) -> Option<Pubkey> {
+        None
+    }
+}
+
+/// Registry entry for a migration, keyed by (discriminator, from_version, to_version).
+pub struct MigrationEntry {
+    pub discriminator: [u8; 8],
+    pub from_version: u8,
+    pub to_version: u8,
+    pub old_len: usize,
+    pub transform: fn(&[u8]) -> Result<Vec<u8>>,
+    pub owner: fn(&[u8]) -> Option<Pubkey>,
+}
+
+impl MigrationEntry {
+    pub const fn of<M: AccountMigration>() -> Self {
+        Self {
+            discriminator: M::DISCRIMINATOR,
+            from_version: M::FROM_VERSION,
+            to_version: M::TO_VERSION,
+            old_len: M::OLD_LEN,
+            transform: transform::<M>,
+            owner: owner::<M>,
+        }
+    }
+}
+
+/// Every migration the program can run. Register new migrations here.
+#[cfg(not(feature = "test-fixtures"))]
+pub const MIGRATIONS: &[MigrationEntry] = &[];
+
+/// Test builds also register the migration hook fixture's record migration.
+#[cfg(feature = "test-fixtures")]
+pub const MIGRATIONS: &[MigrationEntry] = &[
+    MigrationEntry::of::<fixture::FixtureRecordV1ToV2>(),
+];
+
+pub fn find_migration(data: &[u8], from_version: u8, to_version: u8) -> Result<&'static MigrationEntry> {
+    require!(data.len() >= 8, ErrorCode::MigrationNotFound);
+    let migration = MIGRATIONS
+        .iter()
+        .find(|entry| {
+            entry.discriminator[..] == data[..8]
+                && entry.from_version == from_version
+                && entry.to_version == to_version
+        })
+        .ok_or(error!(ErrorCode::MigrationNotFound))?;
+    
+    // Borsh ignores trailing bytes, so a later layout would otherwise parse as the old one
+    require!(
+        data.len() == migration.old_len,
+        ErrorCode::AccountLayoutMismatch
+    );
+    Ok(migration)
+}
+
+/// Produces the new account data. It is never shorter than the old data, so any
+/// space the old layout reserved for growth is kept.
+fn transform<M: AccountMigration>(data: &[u8]) -> Result<Vec<u8>> {
+    let old = M::Old::deserialize(&mut &data[8..])
+        .map_err(|_| error!(ErrorCode::MigrationFailed))?;
+    
+    let mut new_data = M::DISCRIMINATOR.to_vec();
+    M::migrate(old).serialize(&mut new_data)?;
+    
+    if new_data.len() < data.len() {
+        new_data.resize(data.len(), 0);
+    }
+    Ok(new_data)
+}
+
+fn owner<M: AccountMigration>(data: &[u8]) -> Option<Pubkey> {
+    let old = M::Old::deserialize(&mut &data[8..]).ok()?;
+    M::owner(&old)
+}
+
+/// Accounts used to write migrated data through a governed program's migration hook.
+pub struct MigrationWriter<'a, 'info> {
+    pub multisig_config: &'a Account<'info, MultisigConfig>,
+    pub target_program: &'a AccountInfo<'info>,
+    pub migrator: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> MigrationWriter<'a, 'info> {
+    /// Rewrites `account` from the `from_version` layout into the `to_version` layout and
+    /// returns its data before and after. The migrator must hold `ROLE_MIGRATOR` or be the
+    /// owner of an account the migration grows.
+    pub fn migrate(
+        &self,
+        account: &AccountInfo<'info>,
+        from_version: u8,
+        to_version: u8,
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        let old_data = account.try_borrow_data()?.to_vec();
+        let migration = find_migration(&old_data, from_version, to_version)?;
+        let new_data = (migration.transform)(&old_data)?;
+        
+        let owner = (migration.owner)(&old_data).filter(|_| new_data.len() > old_data.len());
+        validate_migrator(self.multisig_config, self.migrator.key, owner)?;
+        
+        if new_data.len() > old_data.len() {
+            self.fund_rent(account, new_data.len())?;
+        }
+        
+        self.write(account, &new_data)?;
+        
+        // The governed program must have written exactly the migrated layout
+        require!(
+            account.try_borrow_data()?[..] == new_data[..],
+            ErrorCode::MigrationFailed
+        );
+        
+        Ok((old_data, new_data))
+    }
+    
+    /// Only the owning program can resize the account, but anyone can fund the rent.
+    fn fund_rent(&self, account: &AccountInfo<'info>, new_len: usize) -> Result<()> {
+        let required = Rent::get()?.minimum_balance(new_len);
+        let shortfall = required.saturating_sub(account.lamports());
+        if shortfall == 0 {
+            return Ok(());
+        }
+        
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.clone(),
+                system_program::Transfer {
+                    from: self.migrator.clone(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )
+    }
+    
+    /// Hands the migrated data to the governed program's migration hook, signed by the
+    /// multisig PDA. The hook reallocs the account to the new length and copies the data in.
+    fn write(&self, account: &AccountInfo<'info>, new_data: &[u8]) -> Result<()> {
+        let mut data = MIGRATION_HOOK_DISCRIMINATOR.to_vec();
+        new_data.to_vec().serialize(&mut data)?;
+        
+        let hook_instruction = Instruction {
+            program_id: self.target_program.key(),
+            accounts: vec![
+                AccountMeta::new(account.key(), false),
+                AccountMeta::new_readonly(self.multisig_config.key(), true),
+            ],
+            data,
+        };
+        
+        let multisig_seeds = &[
+            SEED_MULTISIG,
+            &[self.multisig_config.bump],
+        ];
+        
+        invoke_signed(
+            &hook_instruction,
+            &[
+                account.clone(),
+                self.multisig_config.to_account_info(),
+                self.target_program.clone(),
+            ],
+            &[multisig_seeds],
+        )?;
+        
+        Ok(())
+    }
+}
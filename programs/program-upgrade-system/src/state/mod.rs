@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use crate::error::ErrorCode;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum UpgradeStatus {
@@ -7,40 +9,358 @@ pub enum UpgradeStatus {
     TimelockActive,
     Executed,
     Cancelled,
+    Expired,
+    Rejected,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ProposalAction {
+    Upgrade,
+    RegisterProgram,
+    DeregisterProgram,
+    AddMember { member: Pubkey, roles: u8, weight: u8 },
+    RemoveMember { member: Pubkey },
+    SetMemberRoles { member: Pubkey, roles: u8 },
+    SetMemberWeight { member: Pubkey, weight: u8 },
+    ChangeThreshold { threshold: u16 },
+    SetTimelock { min_timelock: i64, default_timelock: i64 },
+    SetExpiryWindows { approval_window: i64, execution_window: i64 },
+    SetPauseWindow { pause_window: i64 },
+    SetRetentionPeriod { retention_period: i64 },
+    SetCancelThresholds { cancel_threshold: u16, active_cancel_threshold: u16 },
+    SetEmergencyConfig { emergency_threshold: u16, emergency_timelock: i64 },
+    SetUpgradeAuthority { new_authority: Pubkey },
+    SetUpgradeAuthorityChecked { new_authority: Pubkey },
+    FinalizeProgram,
+    ExecuteInstructions,
+}
+
+impl ProposalAction {
+    pub const LEN: usize = 1 + 32 + 1 + 1; // variant + largest payload (member + roles + weight)
+    
+    pub fn is_config_change(&self) -> bool {
+        matches!(
+            self,
+            ProposalAction::AddMember { .. }
+                | ProposalAction::RemoveMember { .. }
+                | ProposalAction::SetMemberRoles { .. }
+                | ProposalAction::SetMemberWeight { .. }
+                | ProposalAction::ChangeThreshold { .. }
+                | ProposalAction::SetTimelock { .. }
+                | ProposalAction::SetExpiryWindows { .. }
+                | ProposalAction::SetPauseWindow { .. }
+                | ProposalAction::SetRetentionPeriod { .. }
+                | ProposalAction::SetCancelThresholds { .. }
+                | ProposalAction::SetEmergencyConfig { .. }
+        )
+    }
+    
+    pub fn is_authority_transfer(&self) -> bool {
+        matches!(
+            self,
+            ProposalAction::SetUpgradeAuthority { .. }
+                | ProposalAction::SetUpgradeAuthorityChecked { .. }
+        )
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct PauseRecord {
+    pub paused_by: Pubkey,
+    pub reason: String,
+    pub paused_at: i64,
+    pub expires_at: Option<i64>, // None once ratified
+    pub ratifications: Vec<Pubkey>, // of the pending scopes once the pause is ratified
+    pub resume_votes: Vec<Pubkey>,
+    pub pending_scopes: u8, // added to a ratified pause, not yet ratified themselves
+    pub pending_expires_at: Option<i64>,
+}
+
+impl PauseRecord {
+    pub const LEN: usize = 32 + 4 + 100 + 8 + 9 + 4 + (32 * 10) + 4 + (32 * 10) + 1 + 9;
+    
+    pub fn is_ratified(&self) -> bool {
+        self.expires_at.is_none()
+    }
+    
+    /// Whether scopes added to the ratified pause lapsed without being ratified.
+    pub fn pending_lapsed(&self, now: i64) -> bool {
+        self.pending_expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Member {
+    pub key: Pubkey,
+    pub roles: u8, // ROLE_* bitmask
+    pub weight: u8,
+}
+
+impl Member {
+    pub const LEN: usize = 32 + 1 + 1;
+    
+    pub fn has_role(&self, role: u8) -> bool {
+        self.roles & role == role
+    }
 }
 
 #[account]
 pub struct MultisigConfig {
     pub authority: Pubkey,
-    pub members: Vec<Pubkey>,
-    pub threshold: u8,
-    pub is_paused: bool,
+    pub members: Vec<Member>,
+    pub threshold: u16, // total approver weight required
+    pub cancel_threshold: u16, // canceller weight required before approval
+    pub active_cancel_threshold: u16, // canceller weight required once approved
+    pub emergency_threshold: u16, // approver weight for emergency proposals, 0 = every approver
+    pub emergency_timelock: i64,
+    pub paused_scopes: u8,
+    pub min_timelock: i64,
+    pub default_timelock: i64,
+    pub approval_window: i64,
+    pub execution_window: i64,
+    pub pause_window: i64,
+    pub retention_period: i64,
+    pub proposal_count: u64,
+    pub pause: Option<PauseRecord>,
     pub bump: u8,
 }
 
 impl MultisigConfig {
-    pub const LEN: usize = 8 + 32 + 4 + (32 * 10) + 1 + 1 + 1; // discriminator + authority + vec_len + members + threshold + is_paused + bump
+    pub const LEN: usize = 8 + 32 + 4 + (Member::LEN * 10) + 2 + 2 + 2 + 2 + 8 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + PauseRecord::LEN + 1; // discriminator + authority + vec_len + members + threshold + cancel_threshold + active_cancel_threshold + emergency_threshold + emergency_timelock + paused_scopes + min_timelock + default_timelock + approval_window + execution_window + pause_window + retention_period + proposal_count + pause + bump
+    
+    pub fn member(&self, key: &Pubkey) -> Option<&Member> {
+        self.members.iter().find(|member| member.key == *key)
+    }
+    
+    pub fn is_member(&self, key: &Pubkey) -> bool {
+        self.member(key).is_some()
+    }
+    
+    pub fn has_role(&self, key: &Pubkey, role: u8) -> bool {
+        self.member(key).is_some_and(|member| member.has_role(role))
+    }
+
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct UpgradeTarget {
+    pub target_program: Pubkey,
+    pub buffer: Pubkey,
+}
+
+/// An extra (program, buffer) pair upgraded together with the proposal's primary target.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct BundledUpgrade {
+    pub target_program: Pubkey,
+    pub buffer: Pubkey,
+    pub buffer_hash: [u8; 32],
+    pub buffer_len: u64,
+}
+
+impl BundledUpgrade {
+    pub const LEN: usize = 32 + 32 + 32 + 8;
 }
 
 #[account]
 pub struct UpgradeProposal {
     pub id: Pubkey,
+    pub index: u64,
     pub proposer: Pubkey,
+    pub action: ProposalAction,
     pub new_program_buffer: Pubkey,
+    pub buffer_hash: [u8; 32],
+    pub buffer_len: u64,
     pub target_program: Pubkey,
+    pub bundled_upgrades: Vec<BundledUpgrade>,
     pub description: String,
+    pub emergency: bool,
     pub status: UpgradeStatus,
     pub approvals: Vec<Pubkey>,
     pub approval_count: u8,
+    pub rejections: Vec<Pubkey>,
+    pub rejection_count: u8,
+    pub cancellations: Vec<Pubkey>,
+    pub cancel_reason: String,
     pub created_at: i64,
     pub timelock_activated_at: Option<i64>,
     pub timelock_period: i64,
     pub executed_at: Option<i64>,
+    pub resolved_at: Option<i64>,
+    pub approval_deadline: i64,
+    pub execution_window: i64,
     pub bump: u8,
 }
 
 impl UpgradeProposal {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 4 + 500 + 1 + 4 + (32 * 10) + 1 + 8 + 9 + 8 + 9 + 1;
+    pub const LEN: usize = 8 + 32 + 8 + 32 + ProposalAction::LEN + 32 + 32 + 8 + 32 + 4 + (BundledUpgrade::LEN * 4) + 4 + 500 + 1 + 1 + 4 + (32 * 10) + 1 + 4 + (32 * 10) + 1 + 4 + (32 * 10) + 4 + 100 + 8 + 9 + 8 + 9 + 9 + 8 + 8 + 1;
+    
+    pub fn is_resolved(&self) -> bool {
+        matches!(
+            self.status,
+            UpgradeStatus::Executed
+                | UpgradeStatus::Cancelled
+                | UpgradeStatus::Expired
+                | UpgradeStatus::Rejected
+        )
+    }
+    
+    /// An upgrade that expired or was rejected still holds its locked buffers; execution
+    /// consumes them and cancellation closes them.
+    pub fn holds_locked_buffers(&self) -> bool {
+        self.action == ProposalAction::Upgrade
+            && matches!(self.status, UpgradeStatus::Expired | UpgradeStatus::Rejected)
+    }
+    
+    /// Fills in a freshly created proposal at the multisig's next index and advances the
+    /// proposal count. Upgrade targets and buffers start empty for the caller to set.
+    pub fn init(
+        proposal: &mut Account<UpgradeProposal>,
+        multisig: &mut MultisigConfig,
+        proposer: Pubkey,
+        action: ProposalAction,
+        description: String,
+        timelock_period: i64,
+        bump: u8,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        
+        proposal.id = proposal.key();
+        proposal.index = multisig.proposal_count;
+        proposal.proposer = proposer;
+        proposal.action = action;
+        proposal.new_program_buffer = Pubkey::default();
+        proposal.buffer_hash = [0; 32];
+        proposal.buffer_len = 0;
+        proposal.target_program = Pubkey::default();
+        proposal.bundled_upgrades = vec![];
+        proposal.description = description;
+        proposal.emergency = false;
+        proposal.status = UpgradeStatus::Proposed;
+        proposal.approvals = vec![];
+        proposal.approval_count = 0;
+        proposal.rejections = vec![];
+        proposal.rejection_count = 0;
+        proposal.cancellations = vec![];
+        proposal.cancel_reason = String::new();
+        proposal.created_at = clock.unix_timestamp;
+        proposal.timelock_activated_at = None;
+        proposal.timelock_period = timelock_period;
+        proposal.executed_at = None;
+        proposal.resolved_at = None;
+        proposal.approval_deadline = clock.unix_timestamp
+            .checked_add(multisig.approval_window)
+            .ok_or(ErrorCode::MathOverflow)?;
+        proposal.execution_window = multisig.execution_window;
+        proposal.bump = bump;
+        
+        multisig.proposal_count = multisig.proposal_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+    
+    /// Whether the proposal upgrades `program`, directly or as part of its bundle.
+    pub fn upgrades(&self, program: &Pubkey) -> bool {
+        self.target_program == *program
+            || self.bundled_upgrades.iter().any(|upgrade| upgrade.target_program == *program)
+    }
+}
+
+#[account]
+pub struct ManagedProgram {
+    pub program_id: Pubkey,
+    pub program_data: Pubkey,
+    pub registered_at: i64,
+    pub finalized: bool,
+    pub bump: u8,
+}
+
+impl ManagedProgram {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct GovernedAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct GovernedInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<GovernedAccountMeta>,
+    pub data: Vec<u8>,
+}
+
+impl GovernedInstruction {
+    pub fn serialized_len(&self) -> usize {
+        32 + 4 + self.accounts.len() * (32 + 1 + 1) + 4 + self.data.len()
+    }
+}
+
+impl From<&GovernedInstruction> for Instruction {
+    fn from(instruction: &GovernedInstruction) -> Self {
+        Instruction {
+            program_id: instruction.program_id,
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|meta| AccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: instruction.data.clone(),
+        }
+    }
+}
+
+/// Instructions replayed by an `ExecuteInstructions` proposal, sized to fit them exactly.
+#[account]
+pub struct ProposalInstructions {
+    pub proposal: Pubkey,
+    pub instructions: Vec<GovernedInstruction>,
+    pub bump: u8,
+}
+
+impl ProposalInstructions {
+    pub fn space(instructions: &[GovernedInstruction]) -> usize {
+        8 + 32 + 4 + instructions.iter().map(GovernedInstruction::serialized_len).sum::<usize>() + 1
+    }
+}
+
+/// Summary of one closed proposal (PDA seeded by the proposal's index).
+#[account]
+pub struct ProposalSummary {
+    pub proposal_id: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub action: ProposalAction,
+    pub target_program: Pubkey,
+    pub buffer_hash: [u8; 32],
+    pub status: UpgradeStatus,
+    pub approval_count: u8,
+    pub created_at: i64,
+    pub resolved_at: i64,
+    pub history_index: u64,
+    pub bump: u8,
+}
+
+impl ProposalSummary {
+    pub const LEN: usize = 8 + 32 + 8 + 32 + ProposalAction::LEN + 32 + 32 + 1 + 1 + 8 + 8 + 8 + 1;
+}
+
+/// Archive counter; each closed proposal's summary lives in its own `ProposalSummary`.
+#[account]
+pub struct ProposalHistory {
+    pub total_archived: u64,
+    pub bump: u8,
+}
+
+impl ProposalHistory {
+    pub const LEN: usize = 8 + 8 + 1; // discriminator + total_archived + bump
 }
 
 #[account]
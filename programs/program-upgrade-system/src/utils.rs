@@ -1,14 +1,86 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    hash::hash,
+    program::invoke_signed,
+};
 use crate::error::ErrorCode;
+use crate::state::*;
+use crate::constants::*;
 
-pub fn validate_multisig_member(members: &Vec<Pubkey>, signer: &Pubkey) -> Result<()> {
+pub fn validate_multisig_member(multisig: &MultisigConfig, signer: &Pubkey) -> Result<()> {
     require!(
-        members.contains(signer),
+        multisig.is_member(signer),
         ErrorCode::UnauthorizedSigner
     );
     Ok(())
 }
 
+/// Checks that `signer` is a member holding every bit of `role`.
+pub fn validate_member_role(multisig: &MultisigConfig, signer: &Pubkey, role: u8) -> Result<()> {
+    validate_multisig_member(multisig, signer)?;
+    require!(
+        multisig.has_role(signer, role),
+        ErrorCode::MissingRole
+    );
+    Ok(())
+}
+
+pub fn validate_weight(weight: u8) -> Result<()> {
+    require!(weight > 0, ErrorCode::InvalidWeight);
+    Ok(())
+}
+
+pub fn validate_roles(roles: u8) -> Result<()> {
+    require!(
+        roles != 0 && roles & !ROLE_ALL == 0,
+        ErrorCode::InvalidRoles
+    );
+    Ok(())
+}
+
+/// Scopes that are currently paused. An unratified pause stops applying once it lapses.
+pub fn active_paused_scopes(multisig: &MultisigConfig, now: i64) -> u8 {
+    match &multisig.pause {
+        Some(record) if record.expires_at.is_some_and(|expires_at| now >= expires_at) => 0,
+        Some(record) if record.pending_lapsed(now) => multisig.paused_scopes & !record.pending_scopes,
+        _ => multisig.paused_scopes,
+    }
+}
+
+pub fn validate_not_paused(multisig: &MultisigConfig, scope: u8) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        active_paused_scopes(multisig, clock.unix_timestamp) & scope == 0,
+        ErrorCode::SystemPaused
+    );
+    Ok(())
+}
+
+/// Sums the weight of votes cast by current approvers, since membership, roles and
+/// weights can change after a vote.
+pub fn tally_member_weight(members: &[Member], votes: &[Pubkey]) -> u16 {
+    tally_role_weight(members, votes, ROLE_APPROVER)
+}
+
+/// Sums the weight of votes cast by current members holding `role`.
+pub fn tally_role_weight(members: &[Member], votes: &[Pubkey], role: u8) -> u16 {
+    votes
+        .iter()
+        .filter_map(|voter| members.iter().find(|member| member.key == *voter))
+        .filter(|member| member.has_role(role))
+        .map(|member| member.weight as u16)
+        .sum()
+}
+
+pub fn validate_pause_scopes(scopes: u8) -> Result<()> {
+    require!(
+        scopes != 0 && scopes & !PAUSE_ALL == 0,
+        ErrorCode::InvalidPauseScope
+    );
+    Ok(())
+}
+
 pub fn validate_timelock_expired(activated_at: i64, period: i64) -> Result<()> {
     let clock = Clock::get()?;
     let current_time = clock.unix_timestamp;
@@ -21,14 +93,520 @@ pub fn validate_timelock_expired(activated_at: i64, period: i64) -> Result<()> {
     Ok(())
 }
 
-pub fn validate_threshold(approval_count: u8, threshold: u8) -> Result<bool> {
-    Ok(approval_count >= threshold)
+pub fn validate_threshold(approval_weight: u16, threshold: u16) -> Result<bool> {
+    Ok(approval_weight >= threshold)
+}
+
+/// Approver weight a proposal needs. Emergency proposals trade their shorter timelock
+/// for a supermajority, which defaults to every approver.
+pub fn required_threshold(proposal: &UpgradeProposal, multisig: &MultisigConfig) -> u16 {
+    if !proposal.emergency {
+        multisig.threshold
+    } else if multisig.emergency_threshold == 0 {
+        role_weight(&multisig.members, ROLE_APPROVER)
+    } else {
+        multisig.emergency_threshold
+    }
+}
+
+/// The threshold stays reachable while enough approvers have not rejected the proposal.
+pub fn validate_threshold_reachable(proposal: &UpgradeProposal, multisig: &MultisigConfig) -> Result<bool> {
+    let rejection_weight = tally_member_weight(&multisig.members, &proposal.rejections);
+    let remaining_weight = role_weight(&multisig.members, ROLE_APPROVER).saturating_sub(rejection_weight);
+    Ok(remaining_weight >= required_threshold(proposal, multisig))
 }
 
-pub fn validate_description_length(description: &String, max_len: usize) -> Result<()> {
+pub fn validate_description_length(description: &str, max_len: usize) -> Result<()> {
     require!(
         description.len() <= max_len,
         ErrorCode::DescriptionTooLong
     );
     Ok(())
 }
+
+pub fn validate_ready_for_execution(proposal: &UpgradeProposal, multisig: &MultisigConfig) -> Result<()> {
+    validate_not_paused(multisig, PAUSE_EXECUTION)?;
+    validate_not_expired(proposal)?;
+    
+    let timelock_activated = proposal.timelock_activated_at
+        .ok_or(ErrorCode::InvalidProposalState)?;
+    
+    validate_timelock_expired(timelock_activated, proposal.timelock_period)?;
+    
+    let approval_weight = tally_member_weight(&multisig.members, &proposal.approvals);
+    
+    require!(
+        validate_threshold(approval_weight, required_threshold(proposal, multisig))?,
+        ErrorCode::InsufficientApprovals
+    );
+    Ok(())
+}
+
+/// Combined weight of the members holding `role`.
+pub fn role_weight(members: &[Member], role: u8) -> u16 {
+    members
+        .iter()
+        .filter(|member| member.has_role(role))
+        .map(|member| member.weight as u16)
+        .sum()
+}
+
+/// Checks that the approval, emergency and cancellation thresholds are reachable by `members`.
+pub fn validate_quorums(
+    members: &[Member],
+    threshold: u16,
+    emergency_threshold: u16,
+    cancel_threshold: u16,
+    active_cancel_threshold: u16,
+) -> Result<()> {
+    let approver_weight = role_weight(members, ROLE_APPROVER);
+    require!(
+        threshold > 0 && threshold <= approver_weight,
+        ErrorCode::InvalidThreshold
+    );
+    // An emergency threshold of zero follows the approver set
+    require!(
+        emergency_threshold == 0
+            || (emergency_threshold >= threshold && emergency_threshold <= approver_weight),
+        ErrorCode::InvalidEmergencyConfig
+    );
+    let canceller_weight = role_weight(members, ROLE_CANCELLER);
+    require!(
+        cancel_threshold > 0
+            && active_cancel_threshold > 0
+            && cancel_threshold <= canceller_weight
+            && active_cancel_threshold <= canceller_weight,
+        ErrorCode::InvalidCancelThreshold
+    );
+    Ok(())
+}
+
+pub fn validate_config_change(multisig: &MultisigConfig, action: &ProposalAction) -> Result<()> {
+    let mut members = multisig.members.clone();
+    let mut threshold = multisig.threshold;
+    let mut emergency_threshold = multisig.emergency_threshold;
+    let mut emergency_timelock = multisig.emergency_timelock;
+    let mut min_timelock = multisig.min_timelock;
+    let mut cancel_threshold = multisig.cancel_threshold;
+    let mut active_cancel_threshold = multisig.active_cancel_threshold;
+    
+    match action {
+        ProposalAction::AddMember { member, roles, weight } => {
+            validate_roles(*roles)?;
+            validate_weight(*weight)?;
+            require!(
+                !multisig.is_member(member),
+                ErrorCode::DuplicateMember
+            );
+            require!(
+                multisig.members.len() < MAX_MULTISIG_MEMBERS,
+                ErrorCode::TooManyMembers
+            );
+            members.push(Member { key: *member, roles: *roles, weight: *weight });
+        }
+        ProposalAction::RemoveMember { member } => {
+            require!(
+                multisig.is_member(member),
+                ErrorCode::NotAMember
+            );
+            members.retain(|m| m.key != *member);
+        }
+        ProposalAction::SetMemberRoles { member, roles } => {
+            validate_roles(*roles)?;
+            let existing = members.iter_mut().find(|m| m.key == *member).ok_or(ErrorCode::NotAMember)?;
+            existing.roles = *roles;
+        }
+        ProposalAction::SetMemberWeight { member, weight } => {
+            validate_weight(*weight)?;
+            let existing = members.iter_mut().find(|m| m.key == *member).ok_or(ErrorCode::NotAMember)?;
+            existing.weight = *weight;
+        }
+        ProposalAction::ChangeThreshold { threshold: new_threshold } => {
+            threshold = *new_threshold;
+        }
+        ProposalAction::SetCancelThresholds { cancel_threshold: pending, active_cancel_threshold: active } => {
+            cancel_threshold = *pending;
+            active_cancel_threshold = *active;
+        }
+        ProposalAction::SetEmergencyConfig { emergency_threshold: new_threshold, emergency_timelock: new_timelock } => {
+            emergency_threshold = *new_threshold;
+            emergency_timelock = *new_timelock;
+        }
+        ProposalAction::SetTimelock { min_timelock: new_min, default_timelock } => {
+            validate_timelock_bounds(*new_min, *default_timelock)?;
+            min_timelock = *new_min;
+        }
+        ProposalAction::SetExpiryWindows { approval_window, execution_window } => {
+            validate_expiry_windows(*approval_window, *execution_window)?;
+        }
+        ProposalAction::SetPauseWindow { pause_window } => {
+            require!(*pause_window > 0, ErrorCode::InvalidPauseWindow);
+        }
+        ProposalAction::SetRetentionPeriod { retention_period } => {
+            require!(*retention_period >= 0, ErrorCode::InvalidRetentionPeriod);
+        }
+        _ => return err!(ErrorCode::InvalidProposalAction),
+    }
+    
+    // The fast path can only ever be shorter than the regular minimum
+    require!(
+        emergency_timelock >= 0 && emergency_timelock <= min_timelock,
+        ErrorCode::InvalidEmergencyConfig
+    );
+    
+    // Every change must leave the quorums reachable by the resulting members
+    validate_quorums(&members, threshold, emergency_threshold, cancel_threshold, active_cancel_threshold)
+}
+
+pub fn get_program_data_address(program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+pub fn get_managed_program_address(program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[SEED_MANAGED_PROGRAM, program_id.as_ref()], &crate::ID).0
+}
+
+/// Loads the registry entry for `program_id` from an account passed outside the
+/// `Accounts` struct, such as a bundled upgrade in `remaining_accounts`.
+pub fn load_managed_program(managed_program: &AccountInfo, program_id: &Pubkey) -> Result<ManagedProgram> {
+    require!(
+        managed_program.key() == get_managed_program_address(program_id)
+            && *managed_program.owner == crate::ID,
+        ErrorCode::InvalidBundleAccounts
+    );
+    
+    let data = managed_program.try_borrow_data()?;
+    ManagedProgram::try_deserialize(&mut &data[..])
+}
+
+/// Checks that a bundle has room and does not name any program or buffer twice, counting
+/// the proposal's primary target and buffer. The loader consumes a buffer on upgrade, so a
+/// shared buffer could never execute.
+pub fn validate_upgrade_bundle(
+    target_program: &Pubkey,
+    buffer: &Pubkey,
+    bundled: &[UpgradeTarget],
+) -> Result<()> {
+    require!(
+        bundled.len() <= MAX_BUNDLED_UPGRADES,
+        ErrorCode::TooManyBundledUpgrades
+    );
+    for (i, upgrade) in bundled.iter().enumerate() {
+        require!(
+            upgrade.target_program != Pubkey::default(),
+            ErrorCode::InvalidTargetProgram
+        );
+        require!(
+            upgrade.target_program != *target_program
+                && !bundled[..i].iter().any(|other| other.target_program == upgrade.target_program),
+            ErrorCode::DuplicateUpgradeTarget
+        );
+        require!(
+            upgrade.buffer != *buffer
+                && !bundled[..i].iter().any(|other| other.buffer == upgrade.buffer),
+            ErrorCode::DuplicateBundleBuffer
+        );
+    }
+    Ok(())
+}
+
+/// Checks that the buffers of an upgrade and its bundle hold at most
+/// `MAX_BUNDLE_PROGRAM_BYTES` in total. Execution hashes every buffer again in one
+/// transaction, so a larger bundle could never fit in the compute budget. Only lengths are
+/// read, so the check is cheap enough to run before any buffer is hashed.
+pub fn validate_bundle_size<'a, 'info: 'a>(
+    buffers: impl IntoIterator<Item = &'a AccountInfo<'info>>,
+) -> Result<()> {
+    let metadata_len = UpgradeableLoaderState::size_of_buffer_metadata();
+    let program_bytes: usize = buffers
+        .into_iter()
+        .map(|buffer| buffer.data_len().saturating_sub(metadata_len))
+        .sum();
+    require!(
+        program_bytes <= MAX_BUNDLE_PROGRAM_BYTES,
+        ErrorCode::BundleTooLarge
+    );
+    Ok(())
+}
+
+/// Checks that `buffer` is a loader buffer whose authority is `authority`, and returns the
+/// hash and length of the program bytes it holds.
+pub fn read_locked_buffer(buffer: &AccountInfo, authority: &Pubkey) -> Result<([u8; 32], u64)> {
+    require!(
+        *buffer.owner == bpf_loader_upgradeable::ID,
+        ErrorCode::InvalidProgramBuffer
+    );
+    
+    let data = buffer.try_borrow_data()?;
+    match UpgradeableLoaderState::try_deserialize(&mut &data[..]) {
+        Ok(UpgradeableLoaderState::Buffer { authority_address }) => {
+            require!(
+                authority_address == Some(*authority),
+                ErrorCode::BufferAuthorityMismatch
+            );
+        }
+        _ => return err!(ErrorCode::InvalidProgramBuffer),
+    }
+    
+    let program_bytes = data
+        .get(UpgradeableLoaderState::size_of_buffer_metadata()..)
+        .ok_or(ErrorCode::InvalidProgramBuffer)?;
+    
+    Ok((hash(program_bytes).to_bytes(), program_bytes.len() as u64))
+}
+
+/// Closes the locked buffers of an upgrade proposal through the loader, signed by the
+/// multisig PDA, and returns the lamports refunded to `rent_recipient`. Bundled buffers are
+/// passed in bundle order. Buffers already closed or consumed are owned by the system
+/// program and skipped.
+pub fn close_upgrade_buffers<'info>(
+    proposal: &UpgradeProposal,
+    buffer: &AccountInfo<'info>,
+    bundled_buffers: &[AccountInfo<'info>],
+    rent_recipient: &AccountInfo<'info>,
+    multisig_config: &Account<'info, MultisigConfig>,
+) -> Result<u64> {
+    require!(
+        buffer.key() == proposal.new_program_buffer,
+        ErrorCode::InvalidProgramBuffer
+    );
+    require!(
+        bundled_buffers.len() == proposal.bundled_upgrades.len(),
+        ErrorCode::InvalidBundleAccounts
+    );
+    
+    let mut reclaimed_lamports = close_buffer(buffer, rent_recipient, multisig_config)?;
+    for (upgrade, buffer) in proposal.bundled_upgrades.iter().zip(bundled_buffers) {
+        require!(
+            buffer.key() == upgrade.buffer,
+            ErrorCode::InvalidProgramBuffer
+        );
+        reclaimed_lamports += close_buffer(buffer, rent_recipient, multisig_config)?;
+    }
+    Ok(reclaimed_lamports)
+}
+
+fn close_buffer<'info>(
+    buffer: &AccountInfo<'info>,
+    rent_recipient: &AccountInfo<'info>,
+    multisig_config: &Account<'info, MultisigConfig>,
+) -> Result<u64> {
+    if *buffer.owner != bpf_loader_upgradeable::ID {
+        return Ok(0);
+    }
+    let lamports = buffer.lamports();
+    
+    let close_instruction = bpf_loader_upgradeable::close(
+        &buffer.key(),
+        &rent_recipient.key(),
+        &multisig_config.key(),
+    );
+    
+    let multisig_seeds = &[
+        SEED_MULTISIG,
+        &[multisig_config.bump],
+    ];
+    
+    invoke_signed(
+        &close_instruction,
+        &[
+            buffer.clone(),
+            rent_recipient.clone(),
+            multisig_config.to_account_info(),
+        ],
+        &[multisig_seeds],
+    )?;
+    
+    Ok(lamports)
+}
+
+/// SHA-256 of an account's full data, as recorded in `AccountVersion`.
+pub fn hash_account_data(data: &[u8]) -> [u8; 32] {
+    hash(data).to_bytes()
+}
+
+/// Migrations may be run by a member holding `ROLE_MIGRATOR`, or by the owner recorded in
+/// the account's own data.
+pub fn validate_migrator(
+    multisig: &MultisigConfig,
+    signer: &Pubkey,
+    account_owner: Option<Pubkey>,
+) -> Result<()> {
+    require!(
+        multisig.has_role(signer, ROLE_MIGRATOR) || account_owner == Some(*signer),
+        ErrorCode::UnauthorizedMigrator
+    );
+    Ok(())
+}
+
+/// Records a completed migration of `account` to `to_version`.
+pub fn record_migration(
+    account_version: &mut AccountVersion,
+    account: Pubkey,
+    to_version: u8,
+    migrated_at: i64,
+    old_data: &[u8],
+    new_data: &[u8],
+) {
+    account_version.account = account;
+    account_version.version = to_version;
+    account_version.migrated = true;
+    account_version.migrated_at = Some(migrated_at);
+    account_version.old_data_hash = hash_account_data(old_data);
+    account_version.new_data_hash = hash_account_data(new_data);
+}
+
+/// Returns the current upgrade authority recorded in a program data account.
+pub fn read_upgrade_authority(program_data: &AccountInfo) -> Result<Option<Pubkey>> {
+    require!(
+        *program_data.owner == bpf_loader_upgradeable::ID,
+        ErrorCode::InvalidProgramData
+    );
+    
+    let data = program_data.try_borrow_data()?;
+    match UpgradeableLoaderState::try_deserialize(&mut &data[..]) {
+        Ok(UpgradeableLoaderState::ProgramData { upgrade_authority_address, .. }) => {
+            Ok(upgrade_authority_address)
+        }
+        _ => err!(ErrorCode::InvalidProgramData),
+    }
+}
+
+/// Fails if `managed_program` is a registry entry for a finalized program. The account is
+/// passed unchecked so that unregistered targets, whose PDA is still empty, pass through.
+pub fn validate_not_finalized(managed_program: &AccountInfo) -> Result<()> {
+    if *managed_program.owner != crate::ID || managed_program.data_is_empty() {
+        return Ok(());
+    }
+    
+    let data = managed_program.try_borrow_data()?;
+    let managed = ManagedProgram::try_deserialize(&mut &data[..])?;
+    require!(!managed.finalized, ErrorCode::ProgramFinalized);
+    Ok(())
+}
+
+/// Governed instructions cannot drive the loader; upgrades, authority changes and
+/// finalization each have their own proposal kind with stricter checks.
+pub fn validate_governed_instructions(instructions: &[GovernedInstruction]) -> Result<()> {
+    require!(
+        !instructions.is_empty() && instructions.len() <= MAX_GOVERNED_INSTRUCTIONS,
+        ErrorCode::TooManyInstructions
+    );
+    for instruction in instructions {
+        require!(
+            instruction.program_id != bpf_loader_upgradeable::ID
+                && instruction.program_id != crate::ID,
+            ErrorCode::InvalidGovernedInstruction
+        );
+    }
+    Ok(())
+}
+
+pub fn validate_upgradeable_program(program: &AccountInfo) -> Result<()> {
+    require!(
+        program.executable && *program.owner == bpf_loader_upgradeable::ID,
+        ErrorCode::ProgramNotUpgradeable
+    );
+    Ok(())
+}
+
+pub fn validate_timelock_bounds(min_timelock: i64, default_timelock: i64) -> Result<()> {
+    require!(
+        min_timelock >= 0 && min_timelock <= default_timelock && default_timelock <= MAX_TIMELOCK,
+        ErrorCode::InvalidTimelock
+    );
+    Ok(())
+}
+
+/// Writes a resolved proposal's summary and counts it in the history. Returns the
+/// summary's position in the archive.
+pub fn archive_proposal(
+    history: &mut ProposalHistory,
+    summary: &mut ProposalSummary,
+    proposal: &UpgradeProposal,
+    resolved_at: i64,
+    bump: u8,
+) -> Result<u64> {
+    let history_index = history.total_archived;
+    
+    summary.proposal_id = proposal.id;
+    summary.index = proposal.index;
+    summary.proposer = proposal.proposer;
+    summary.action = proposal.action;
+    summary.target_program = proposal.target_program;
+    summary.buffer_hash = proposal.buffer_hash;
+    summary.status = proposal.status;
+    summary.approval_count = proposal.approval_count;
+    summary.created_at = proposal.created_at;
+    summary.resolved_at = resolved_at;
+    summary.history_index = history_index;
+    summary.bump = bump;
+    
+    history.total_archived = history.total_archived
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(history_index)
+}
+
+/// Finalization is irreversible, so it never runs on less than `FINALIZE_TIMELOCK`.
+pub fn resolve_finalize_timelock(timelock_period: i64) -> i64 {
+    timelock_period.max(FINALIZE_TIMELOCK)
+}
+
+/// Proposals may ask for a longer timelock than the multisig default, never a shorter one.
+pub fn resolve_timelock_period(multisig: &MultisigConfig, requested: Option<i64>) -> Result<i64> {
+    let period = requested.unwrap_or(multisig.default_timelock);
+    require!(
+        period >= multisig.default_timelock
+            && period >= multisig.min_timelock
+            && period <= MAX_TIMELOCK,
+        ErrorCode::InvalidTimelock
+    );
+    Ok(period)
+}
+
+/// Emergency proposals wait at least `emergency_timelock`, which may be shorter than the
+/// regular minimum.
+pub fn resolve_emergency_timelock_period(multisig: &MultisigConfig, requested: Option<i64>) -> Result<i64> {
+    let period = requested.unwrap_or(multisig.emergency_timelock);
+    require!(
+        period >= multisig.emergency_timelock && period <= MAX_TIMELOCK,
+        ErrorCode::InvalidTimelock
+    );
+    Ok(period)
+}
+
+pub fn validate_expiry_windows(approval_window: i64, execution_window: i64) -> Result<()> {
+    require!(
+        approval_window > 0 && execution_window > 0,
+        ErrorCode::InvalidExpiryWindow
+    );
+    Ok(())
+}
+
+/// A proposal expires if it misses its approval deadline, or if it is not executed
+/// within the execution window after its timelock ends.
+pub fn is_proposal_expired(proposal: &UpgradeProposal, now: i64) -> Result<bool> {
+    match proposal.status {
+        UpgradeStatus::Proposed | UpgradeStatus::Approved => Ok(now > proposal.approval_deadline),
+        UpgradeStatus::TimelockActive => {
+            let activated_at = proposal.timelock_activated_at
+                .ok_or(ErrorCode::InvalidProposalState)?;
+            let execution_deadline = activated_at
+                .checked_add(proposal.timelock_period)
+                .and_then(|t| t.checked_add(proposal.execution_window))
+                .ok_or(ErrorCode::MathOverflow)?;
+            Ok(now > execution_deadline)
+        }
+        _ => Ok(false),
+    }
+}
+
+pub fn validate_not_expired(proposal: &UpgradeProposal) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        !is_proposal_expired(proposal, clock.unix_timestamp)?,
+        ErrorCode::ProposalExpired
+    );
+    Ok(())
+}
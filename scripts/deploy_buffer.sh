@@ -24,9 +24,18 @@ BUFFER_OUTPUT=$(solana program write-buffer \
 BUFFER_ADDRESS=$(echo "$BUFFER_OUTPUT" | grep -oE '[A-Za-z0-9]{32,44}' | head -1)
 
 echo "✅ Buffer created: $BUFFER_ADDRESS"
+
+# propose_upgrade only accepts buffers whose authority is the multisig PDA
+if [ -n "$MULTISIG_PDA" ]; then
+    echo "🔒 Handing buffer authority to multisig $MULTISIG_PDA..."
+    solana program set-buffer-authority "$BUFFER_ADDRESS" \
+        --new-buffer-authority "$MULTISIG_PDA" \
+        --keypair "$KEYPAIR_PATH"
+fi
 echo ""
 echo "Next steps:"
 echo "1. Verify the buffer: solana program show $BUFFER_ADDRESS"
-echo "2. Propose upgrade with this buffer address"
+echo "2. If MULTISIG_PDA was not set: solana program set-buffer-authority $BUFFER_ADDRESS --new-buffer-authority <MULTISIG_PDA>"
+echo "3. Propose upgrade with this buffer address"
 echo ""
 echo "Buffer Address: $BUFFER_ADDRESS"
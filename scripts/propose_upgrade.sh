@@ -40,5 +40,5 @@ echo "Proposal ID: $PROPOSAL_ID"
 echo ""
 echo "Next steps:"
 echo "1. Get multisig members to approve: POST /proposals/$PROPOSAL_ID/approve"
-echo "2. Wait for timelock (48 hours)"
+echo "2. Wait for timelock (multisig default, or the period requested at proposal time)"
 echo "3. Execute upgrade: POST /proposals/$PROPOSAL_ID/execute"
@@ -1,12 +1,16 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { ProgramUpgradeSystem } from "../target/types/program_upgrade_system";
+import { MigrationHookFixture } from "../target/types/migration_hook_fixture";
+import { createHash } from "crypto";
+import { readFileSync } from "fs";
 
 describe("program-upgrade-system", () => {
   // Configure the client to use the local cluster.
   anchor.setProvider(anchor.AnchorProvider.env());
 
   const program = anchor.workspace.programUpgradeSystem as Program<ProgramUpgradeSystem>;
+  const fixture = anchor.workspace.migrationHookFixture as Program<MigrationHookFixture>;
   // const idl = require("../target/idl/program_upgrade_system.json");
   // const programId = new anchor.web3.PublicKey("F4rYDGUKQHtJt14aPyGxtzacx9x7x9MH7rper2TuRdVz");
   // const program = new anchor.Program(idl, programId, anchor.getProvider());
@@ -14,16 +18,153 @@ describe("program-upgrade-system", () => {
   let buffer: anchor.web3.PublicKey;
   let proposalPda: anchor.web3.PublicKey;
   let multisigConfigPda: anchor.web3.PublicKey;
+  let proposalHistoryPda: anchor.web3.PublicKey;
+
+  const BPF_LOADER_UPGRADEABLE = new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+
+  // Registry entry for this program, the target of every upgrade proposal below
+  const [selfManagedProgramPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("managed_program"), program.programId.toBuffer()],
+    program.programId
+  );
+
+  // Proposals are seeded by the multisig's running proposal_count
+  async function nextProposalPda(): Promise<anchor.web3.PublicKey> {
+    const config = await program.account.multisigConfig.fetch(multisigConfigPda);
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), config.proposalCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+  }
+
+  // Creates a loader buffer holding `programBytes` (placeholder bytes by default) and hands
+  // its authority to the multisig PDA, which propose_upgrade requires before it will lock
+  // the buffer hash.
+  async function createLockedBuffer(
+    programBytes: Buffer = Buffer.from("placeholder program bytes")
+  ): Promise<anchor.web3.PublicKey> {
+    const provider = anchor.getProvider() as anchor.AnchorProvider;
+    const bufferKeypair = anchor.web3.Keypair.generate();
+    const space = 37 + programBytes.length; // buffer metadata + program bytes
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(space);
+
+    const initializeData = Buffer.alloc(4); // InitializeBuffer
+    const setAuthorityData = Buffer.alloc(4);
+    setAuthorityData.writeUInt32LE(4, 0); // SetAuthority
+
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.createAccount({
+          fromPubkey: provider.publicKey,
+          newAccountPubkey: bufferKeypair.publicKey,
+          lamports,
+          space,
+          programId: BPF_LOADER_UPGRADEABLE,
+        }),
+        new anchor.web3.TransactionInstruction({
+          programId: BPF_LOADER_UPGRADEABLE,
+          keys: [
+            { pubkey: bufferKeypair.publicKey, isSigner: false, isWritable: true },
+            { pubkey: provider.publicKey, isSigner: false, isWritable: false },
+          ],
+          data: initializeData,
+        })
+      ),
+      [bufferKeypair]
+    );
+
+    // Real program binaries do not fit in one transaction, so write them in chunks
+    const chunkSize = 900;
+    for (let offset = 0; offset < programBytes.length; offset += chunkSize) {
+      const chunk = programBytes.subarray(offset, offset + chunkSize);
+      const writeData = Buffer.alloc(16 + chunk.length);
+      writeData.writeUInt32LE(1, 0); // Write
+      writeData.writeUInt32LE(offset, 4);
+      writeData.writeBigUInt64LE(BigInt(chunk.length), 8);
+      chunk.copy(writeData, 16);
+
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          new anchor.web3.TransactionInstruction({
+            programId: BPF_LOADER_UPGRADEABLE,
+            keys: [
+              { pubkey: bufferKeypair.publicKey, isSigner: false, isWritable: true },
+              { pubkey: provider.publicKey, isSigner: true, isWritable: false },
+            ],
+            data: writeData,
+          })
+        )
+      );
+    }
+
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        new anchor.web3.TransactionInstruction({
+          programId: BPF_LOADER_UPGRADEABLE,
+          keys: [
+            { pubkey: bufferKeypair.publicKey, isSigner: false, isWritable: true },
+            { pubkey: provider.publicKey, isSigner: true, isWritable: false },
+            { pubkey: multisigConfigPda, isSigner: false, isWritable: false },
+          ],
+          data: setAuthorityData,
+        })
+      )
+    );
+
+    return bufferKeypair.publicKey;
+  }
+
+  // A fresh key funded by the wallet, to pay for the accounts it creates as a signer
+  async function fundedKeypair(
+    lamports = anchor.web3.LAMPORTS_PER_SOL / 10
+  ): Promise<anchor.web3.Keypair> {
+    const provider = anchor.getProvider() as anchor.AnchorProvider;
+    const keypair = anchor.web3.Keypair.generate();
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.publicKey,
+          toPubkey: keypair.publicKey,
+          lamports,
+        })
+      )
+    );
+    return keypair;
+  }
+
+  // Fails the test unless `call` is rejected with the program error `code`
+  async function expectError(call: Promise<unknown>, code: string): Promise<void> {
+    try {
+      await call;
+    } catch (e: any) {
+      const actual = e.error?.errorCode?.code;
+      if (actual !== code) {
+        throw new Error(`Expected ${code}, got ${actual ?? e.message}`);
+      }
+      return;
+    }
+    throw new Error(`Expected ${code}, but the call succeeded`);
+  }
 
   it("Is initialized!", async () => {
     const authority = anchor.getProvider().publicKey;
-    const members = [authority];
+    // Every role: proposer, approver, executor, canceller, pauser, migrator
+    const members = [{ key: authority, roles: 0b111111, weight: 1 }];
     const threshold = 1;
+    // No timelock on localnet so approved proposals can be executed in tests
+    const minTimelock = new anchor.BN(0);
+    const defaultTimelock = new anchor.BN(0);
+    const approvalWindow = new anchor.BN(7 * 24 * 60 * 60);
+    const executionWindow = new anchor.BN(7 * 24 * 60 * 60);
 
     [multisigConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
       [Buffer.from("multisig")],
       program.programId
     );
+    [proposalHistoryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("history")],
+      program.programId
+    );
 
     console.log("Authority:", authority.toBase58());
     console.log("MultisigConfig PDA:", multisigConfigPda.toBase58());
@@ -39,9 +180,17 @@ describe("program-upgrade-system", () => {
     }
 
     const tx = await program.methods
-      .initializeMultisig(members, threshold)
+      .initializeMultisig(
+        members,
+        threshold,
+        minTimelock,
+        defaultTimelock,
+        approvalWindow,
+        executionWindow
+      )
       .accounts({
         multisigConfig: multisigConfigPda,
+        proposalHistory: proposalHistoryPda,
         authority: authority,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -51,21 +200,20 @@ describe("program-upgrade-system", () => {
   });
 
   it("Proposes an upgrade", async () => {
-    buffer = anchor.web3.Keypair.generate().publicKey;
+    buffer = await createLockedBuffer();
     const description = "Test upgrade proposal";
 
-    [proposalPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("proposal"), buffer.toBuffer()],
-      program.programId
-    );
+    proposalPda = await nextProposalPda();
 
     console.log("Proposal PDA:", proposalPda.toBase58());
 
     const tx = await program.methods
-      .proposeUpgrade(buffer, description)
+      .proposeUpgrade(buffer, program.programId, description, null, false, [])
       .accounts({
         proposal: proposalPda,
         multisigConfig: multisigConfigPda,
+        buffer: buffer,
+        managedProgram: selfManagedProgramPda,
         proposer: anchor.getProvider().publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -97,9 +245,14 @@ describe("program-upgrade-system", () => {
     // However, since we can't easily fake the BPF loader interaction here without complicated setup,
     // we will assert that the instruction is reachable and fails on constraints (TimeLock).
 
+    const [managedProgramPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("managed_program"), program.programId.toBuffer()],
+      program.programId
+    );
+
     const programDataAddress = anchor.web3.PublicKey.findProgramAddressSync(
       [program.programId.toBuffer()],
-      new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      BPF_LOADER_UPGRADEABLE
     )[0];
 
     try {
@@ -108,12 +261,13 @@ describe("program-upgrade-system", () => {
         .accounts({
           proposal: proposalPda,
           multisigConfig: multisigConfigPda,
+          managedProgram: managedProgramPda,
           programToUpgrade: program.programId,
           programData: programDataAddress, // Dummy address for simulation
           buffer: buffer,
           spillAccount: anchor.getProvider().publicKey,
           executor: anchor.getProvider().publicKey,
-          bpfLoaderUpgradeable: new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111"),
+          bpfLoaderUpgradeable: BPF_LOADER_UPGRADEABLE,
           // rent: anchor.web3.SYSVAR_RENT_PUBKEY, // Auto-resolved
           // clock: anchor.web3.SYSVAR_CLOCK_PUBKEY, // Auto-resolved
         })
@@ -127,18 +281,17 @@ describe("program-upgrade-system", () => {
 
   it("Cancels an upgrade", async () => {
     // 1. Create a FRESH proposal to cancel (don't use the approved one)
-    const cancelBuffer = anchor.web3.Keypair.generate().publicKey;
-    const [cancelProposalPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("proposal"), cancelBuffer.toBuffer()],
-      program.programId
-    );
+    const cancelBuffer = await createLockedBuffer();
+    const cancelProposalPda = await nextProposalPda();
 
     // Create it first
     await program.methods
-      .proposeUpgrade(cancelBuffer, "To be cancelled")
+      .proposeUpgrade(cancelBuffer, program.programId, "To be cancelled", null, false, [])
       .accounts({
         proposal: cancelProposalPda,
         multisigConfig: multisigConfigPda,
+        buffer: cancelBuffer,
+        managedProgram: selfManagedProgramPda,
         proposer: anchor.getProvider().publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -146,13 +299,18 @@ describe("program-upgrade-system", () => {
 
     // 2. Now Cancel it
     const tx = await program.methods
-      .cancelUpgrade(cancelProposalPda)
+      .cancelUpgrade(cancelProposalPda, "Superseded by a newer build", false)
       .accounts({
         proposal: cancelProposalPda,
         multisigConfig: multisigConfigPda,
         canceller: anchor.getProvider().publicKey, // Must be a member
-        buffer: cancelBuffer, // Required by instruction constraint
+        buffer: cancelBuffer, // Closed and refunded to the proposer
         rentRecipient: anchor.getProvider().publicKey,
+        bpfLoaderUpgradeable: BPF_LOADER_UPGRADEABLE,
+        proposalHistory: null,
+        proposalSummary: null,
+        proposalInstructions: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
 
@@ -167,261 +325,1515 @@ describe("program-upgrade-system", () => {
     if (!proposalAccount.status.cancelled) {
       throw new Error("Proposal was not marked as cancelled!");
     }
+
+    // The reason and the cancellers are kept for the audit trail
+    if (proposalAccount.cancelReason !== "Superseded by a newer build") {
+      throw new Error("Cancel reason was not recorded");
+    }
+    if (!proposalAccount.cancellations[0].equals(anchor.getProvider().publicKey)) {
+      throw new Error("Canceller was not recorded");
+    }
   });
 
-  it("Migrates an account", async () => {
-    // 1. Initialize a dummy account to represent an "Old" account
-    const oldAccount = anchor.web3.Keypair.generate();
-    // We just need a pubkey, the instruction checks the key matches. 
-    // In reality, this would be an actual state account.
+  it("Closes the buffer and archives the proposal when cancelling with close_proposal", async () => {
+    const closeBuffer = await createLockedBuffer();
+    const closeProposalPda = await nextProposalPda();
 
-    const [accountVersionPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("migration"), oldAccount.publicKey.toBuffer()],
+    await program.methods
+      .proposeUpgrade(closeBuffer, program.programId, "Cancel and close", null, false, [])
+      .accounts({
+        proposal: closeProposalPda,
+        multisigConfig: multisigConfigPda,
+        buffer: closeBuffer,
+        managedProgram: selfManagedProgramPda,
+        proposer: anchor.getProvider().publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const { index } = await program.account.upgradeProposal.fetch(closeProposalPda);
+    const [closeSummaryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal_summary"), index.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
 
-    const tx = await program.methods
-      .migrateAccount(oldAccount.publicKey)
+    await program.methods
+      .cancelUpgrade(closeProposalPda, "Withdrawn", true)
       .accounts({
-        accountVersion: accountVersionPda,
-        oldAccount: oldAccount.publicKey,
-        migrator: anchor.getProvider().publicKey,
+        proposal: closeProposalPda,
+        multisigConfig: multisigConfigPda,
+        canceller: anchor.getProvider().publicKey,
+        buffer: closeBuffer,
+        rentRecipient: anchor.getProvider().publicKey,
+        bpfLoaderUpgradeable: BPF_LOADER_UPGRADEABLE,
+        proposalHistory: proposalHistoryPda,
+        proposalSummary: closeSummaryPda,
+        proposalInstructions: null,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
 
-    console.log("Migrate transaction signature", tx);
-
-    const versionAccount = await program.account.accountVersion.fetch(accountVersionPda);
-    // The field in the IDL/Account struct is likely just `version` or `new_version` converted to camelCase
-    // Let's check IDL or assume `version` (based on view_file of migrate_account.rs: account_version.version = 2)
-    if (versionAccount.version !== 2) {
-      throw new Error("Migration version mismatch!");
+    const connection = anchor.getProvider().connection;
+    if (await connection.getAccountInfo(closeBuffer)) {
+      throw new Error("Buffer was not closed!");
     }
-    if (!versionAccount.migrated) {
-      throw new Error("Migration flag not set!");
+    if (await connection.getAccountInfo(closeProposalPda)) {
+      throw new Error("Proposal was not closed!");
+    }
+    const summary = await program.account.proposalSummary.fetch(closeSummaryPda);
+    if (!summary.proposalId.equals(closeProposalPda) || !summary.status.cancelled) {
+      throw new Error("Cancelled proposal was not archived!");
     }
   });
 
-  // ==================== EDGE CASE TESTS ====================
+  it("Bundles a second program into an upgrade and closes both buffers on cancel", async () => {
+    const primaryBuffer = await createLockedBuffer();
+    const adapterBuffer = await createLockedBuffer();
+    const adapterProgram = anchor.web3.Keypair.generate().publicKey;
+    const [adapterManagedProgramPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("managed_program"), adapterProgram.toBuffer()],
+      program.programId
+    );
+    const bundleProposalPda = await nextProposalPda();
 
-  describe("Edge Cases", () => {
-    it("Prevents duplicate approval from the same member", async () => {
-      // Create a fresh proposal
-      const dupBuffer = anchor.web3.Keypair.generate().publicKey;
-      const [dupProposalPda] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("proposal"), dupBuffer.toBuffer()],
-        program.programId
-      );
+    await program.methods
+      .proposeUpgrade(primaryBuffer, program.programId, "Upgrade core with adapter", null, false, [
+        { targetProgram: adapterProgram, buffer: adapterBuffer },
+      ])
+      .accounts({
+        proposal: bundleProposalPda,
+        multisigConfig: multisigConfigPda,
+        buffer: primaryBuffer,
+        managedProgram: selfManagedProgramPda,
+        proposer: anchor.getProvider().publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        { pubkey: adapterBuffer, isSigner: false, isWritable: false },
+        { pubkey: adapterManagedProgramPda, isSigner: false, isWritable: false },
+      ])
+      .rpc();
 
-      await program.methods
-        .proposeUpgrade(dupBuffer, "Test duplicate approval")
-        .accounts({
-          proposal: dupProposalPda,
-          multisigConfig: multisigConfigPda,
-          proposer: anchor.getProvider().publicKey,
-          systemProgram: anchor.web3.SystemProgram.programId,
-        })
-        .rpc();
+    const proposalAccount = await program.account.upgradeProposal.fetch(bundleProposalPda);
+    if (proposalAccount.bundledUpgrades.length !== 1 ||
+        !proposalAccount.bundledUpgrades[0].targetProgram.equals(adapterProgram)) {
+      throw new Error("Bundled upgrade was not recorded!");
+    }
 
-      // First approval should succeed
-      await program.methods
-        .approveUpgrade(dupProposalPda)
-        .accounts({
-          proposal: dupProposalPda,
-          multisigConfig: multisigConfigPda,
-          approver: anchor.getProvider().publicKey,
-        })
-        .rpc();
+    await program.methods
+      .cancelUpgrade(bundleProposalPda, "Withdrawn", false)
+      .accounts({
+        proposal: bundleProposalPda,
+        multisigConfig: multisigConfigPda,
+        canceller: anchor.getProvider().publicKey,
+        buffer: primaryBuffer,
+        rentRecipient: anchor.getProvider().publicKey,
+        bpfLoaderUpgradeable: BPF_LOADER_UPGRADEABLE,
+        proposalHistory: null,
+        proposalSummary: null,
+        proposalInstructions: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        { pubkey: adapterBuffer, isSigner: false, isWritable: true },
+      ])
+      .rpc();
 
-      // Second approval from same member should fail
-      try {
-        await program.methods
-          .approveUpgrade(dupProposalPda)
-          .accounts({
-            proposal: dupProposalPda,
-            multisigConfig: multisigConfigPda,
-            approver: anchor.getProvider().publicKey,
-          })
-          .rpc();
-        throw new Error("Should have failed - duplicate approval");
-      } catch (e: any) {
-        console.log("Expected failure (duplicate approval):", e.message);
-        if (!e.message.includes("AlreadyApproved")) {
-          // Check for the expected error
-          console.log("Note: Got different error than AlreadyApproved");
-        }
-      }
-    });
+    const connection = anchor.getProvider().connection;
+    if (await connection.getAccountInfo(primaryBuffer) || await connection.getAccountInfo(adapterBuffer)) {
+      throw new Error("Bundle buffers were not closed!");
+    }
+  });
 
-    it("Prevents cancelling an already cancelled proposal", async () => {
-      // Create and cancel a proposal
-      const cancelBuffer2 = anchor.web3.Keypair.generate().publicKey;
-      const [cancelProposalPda2] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("proposal"), cancelBuffer2.toBuffer()],
-        program.programId
-      );
+  it("Rejects a bundle that names the same program twice", async () => {
+    const primaryBuffer = await createLockedBuffer();
+    const duplicateBuffer = await createLockedBuffer();
+    const duplicateProposalPda = await nextProposalPda();
 
-      await program.methods
-        .proposeUpgrade(cancelBuffer2, "To be double-cancelled")
+    await expectError(
+      program.methods
+        .proposeUpgrade(primaryBuffer, program.programId, "Duplicate target", null, false, [
+          { targetProgram: program.programId, buffer: duplicateBuffer },
+        ])
         .accounts({
-          proposal: cancelProposalPda2,
+          proposal: duplicateProposalPda,
           multisigConfig: multisigConfigPda,
+          buffer: primaryBuffer,
+          managedProgram: selfManagedProgramPda,
           proposer: anchor.getProvider().publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
-        .rpc();
-
-      // First cancel
-      await program.methods
-        .cancelUpgrade(cancelProposalPda2)
-        .accounts({
-          proposal: cancelProposalPda2,
-          multisigConfig: multisigConfigPda,
-          canceller: anchor.getProvider().publicKey,
-          buffer: cancelBuffer2,
-          rentRecipient: anchor.getProvider().publicKey,
-        })
-        .rpc();
-
-      // Second cancel should fail
-      try {
-        await program.methods
-          .cancelUpgrade(cancelProposalPda2)
-          .accounts({
-            proposal: cancelProposalPda2,
-            multisigConfig: multisigConfigPda,
-            canceller: anchor.getProvider().publicKey,
-            buffer: cancelBuffer2,
-            rentRecipient: anchor.getProvider().publicKey,
-          })
-          .rpc();
-        throw new Error("Should have failed - double cancel");
-      } catch (e: any) {
-        console.log("Expected failure (double cancel):", e.message);
-        if (!e.message.includes("ProposalAlreadyCancelled")) {
-          console.log("Note: Got different error than ProposalAlreadyCancelled");
-        }
-      }
-    });
+        .remainingAccounts([
+          { pubkey: duplicateBuffer, isSigner: false, isWritable: false },
+          { pubkey: selfManagedProgramPda, isSigner: false, isWritable: false },
+        ])
+        .rpc(),
+      "DuplicateUpgradeTarget"
+    );
+  });
 
-    it("Verifies proposal state after approval", async () => {
-      // Create and approve a proposal, then verify state
-      const stateBuffer = anchor.web3.Keypair.generate().publicKey;
-      const [stateProposalPda] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("proposal"), stateBuffer.toBuffer()],
-        program.programId
-      );
+  it("Rejects a bundle that reuses the primary buffer", async () => {
+    const primaryBuffer = await createLockedBuffer();
+    const sharedProposalPda = await nextProposalPda();
+    const adapterProgram = anchor.web3.Keypair.generate().publicKey;
+    const [adapterManagedProgramPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("managed_program"), adapterProgram.toBuffer()],
+      program.programId
+    );
 
-      await program.methods
-        .proposeUpgrade(stateBuffer, "State verification test")
+    await expectError(
+      program.methods
+        .proposeUpgrade(primaryBuffer, program.programId, "Shared buffer", null, false, [
+          { targetProgram: adapterProgram, buffer: primaryBuffer },
+        ])
         .accounts({
-          proposal: stateProposalPda,
+          proposal: sharedProposalPda,
           multisigConfig: multisigConfigPda,
+          buffer: primaryBuffer,
+          managedProgram: selfManagedProgramPda,
           proposer: anchor.getProvider().publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
-        .rpc();
+        .remainingAccounts([
+          { pubkey: primaryBuffer, isSigner: false, isWritable: false },
+          { pubkey: adapterManagedProgramPda, isSigner: false, isWritable: false },
+        ])
+        .rpc(),
+      "DuplicateBundleBuffer"
+    );
+  });
 
-      // Check initial state
-      let proposal = await program.account.upgradeProposal.fetch(stateProposalPda);
-      console.log("Initial status:", proposal.status);
-      console.log("Initial approvals:", proposal.approvals);
+  it("Registers a managed program through governance", async () => {
+    const registerProposalPda = await nextProposalPda();
 
-      // Approve
-      await program.methods
-        .approveUpgrade(stateProposalPda)
-        .accounts({
-          proposal: stateProposalPda,
-          multisigConfig: multisigConfigPda,
-          approver: anchor.getProvider().publicKey,
-        })
-        .rpc();
+    const tx = await program.methods
+      .proposeAction({ registerProgram: {} }, program.programId, "Register self", null)
+      .accounts({
+        proposal: registerProposalPda,
+        multisigConfig: multisigConfigPda,
+        proposer: anchor.getProvider().publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
 
-      // Check state after approval
-      proposal = await program.account.upgradeProposal.fetch(stateProposalPda);
-      console.log("Status after approval:", proposal.status);
-      console.log("Approvals after:", proposal.approvals);
+    console.log("Register proposal transaction signature", tx);
 
-      // Verify approval count increased
-      if (proposal.approvals.length === 0) {
-        throw new Error("Approval was not recorded!");
-      }
-      console.log("✓ Proposal state verified successfully");
-    });
+    const proposalAccount = await program.account.upgradeProposal.fetch(registerProposalPda);
+    if (!proposalAccount.action.registerProgram) {
+      throw new Error("Proposal action was not RegisterProgram!");
+    }
+    if (!proposalAccount.targetProgram.equals(program.programId)) {
+      throw new Error("Proposal target mismatch!");
+    }
+
+    // With a zero timelock the proposal can be executed right after approval
+    await program.methods
+      .approveUpgrade(registerProposalPda)
+      .accounts({
+        proposal: registerProposalPda,
+        multisigConfig: multisigConfigPda,
+        approver: anchor.getProvider().publicKey,
+      })
+      .rpc();
+
+    const [managedProgramPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("managed_program"), program.programId.toBuffer()],
+      program.programId
+    );
+    const programDataAddress = anchor.web3.PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE
+    )[0];
+
+    await program.methods
+      .registerProgram()
+      .accounts({
+        proposal: registerProposalPda,
+        multisigConfig: multisigConfigPda,
+        managedProgram: managedProgramPda,
+        program: program.programId,
+        programData: programDataAddress,
+        executor: anchor.getProvider().publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const managedProgram = await program.account.managedProgram.fetch(managedProgramPda);
+    if (!managedProgram.programData.equals(programDataAddress)) {
+      throw new Error("Managed program data address mismatch!");
+    }
   });
 
-  // ==================== PAUSE/RESUME TESTS ====================
+  it("Moves upgrade authority into the multisig and back through governance", async () => {
+    const wallet = anchor.getProvider().publicKey;
+    const [managedProgramPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("managed_program"), program.programId.toBuffer()],
+      program.programId
+    );
+    const programDataAddress = anchor.web3.PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE
+    )[0];
 
-  describe("Pause/Resume System", () => {
-    it("Pauses the system", async () => {
-      const tx = await program.methods
-        .pauseSystem()
+    // Each hop is checked, so both the outgoing and incoming authority sign
+    const hops = [
+      { current: wallet, next: multisigConfigPda },
+      { current: multisigConfigPda, next: wallet },
+    ];
+
+    for (const { current, next } of hops) {
+      const transferProposalPda = await nextProposalPda();
+      await program.methods
+        .proposeAction(
+          { setUpgradeAuthorityChecked: { newAuthority: next } },
+          program.programId,
+          "Move upgrade authority",
+          null
+        )
         .accounts({
+          proposal: transferProposalPda,
           multisigConfig: multisigConfigPda,
-          pauser: anchor.getProvider().publicKey,
+          proposer: wallet,
+          systemProgram: anchor.web3.SystemProgram.programId,
         })
         .rpc();
 
-      console.log("Pause transaction signature", tx);
-
-      // Verify system is paused
-      const config = await program.account.multisigConfig.fetch(multisigConfigPda);
-      if (!config.isPaused) {
-        throw new Error("System was not paused!");
-      }
-      console.log("✓ System paused successfully");
-    });
-
-    it("Resumes the system", async () => {
-      const tx = await program.methods
-        .resumeSystem()
+      await program.methods
+        .approveUpgrade(transferProposalPda)
         .accounts({
+          proposal: transferProposalPda,
           multisigConfig: multisigConfigPda,
-          resumer: anchor.getProvider().publicKey,
+          approver: wallet,
         })
         .rpc();
 
-      console.log("Resume transaction signature", tx);
-
-      // Verify system is resumed
-      const config = await program.account.multisigConfig.fetch(multisigConfigPda);
-      if (config.isPaused) {
-        throw new Error("System was not resumed!");
-      }
-      console.log("✓ System resumed successfully");
-    });
-
-    it("Prevents double pause", async () => {
-      // First pause
       await program.methods
-        .pauseSystem()
+        .executeAuthorityTransfer()
         .accounts({
+          proposal: transferProposalPda,
           multisigConfig: multisigConfigPda,
-          pauser: anchor.getProvider().publicKey,
+          managedProgram: managedProgramPda,
+          programData: programDataAddress,
+          currentAuthority: current,
+          newAuthority: next,
+          executor: wallet,
+          bpfLoaderUpgradeable: BPF_LOADER_UPGRADEABLE,
         })
         .rpc();
 
-      // Second pause should fail
-      try {
-        await program.methods
-          .pauseSystem()
-          .accounts({
-            multisigConfig: multisigConfigPda,
-            pauser: anchor.getProvider().publicKey,
-          })
-          .rpc();
-        throw new Error("Should have failed - double pause");
-      } catch (e: any) {
-        console.log("Expected failure (double pause):", e.message);
-        if (!e.message.includes("SystemAlreadyPaused")) {
-          console.log("Note: Got different error than SystemAlreadyPaused");
-        }
+      const programData = await anchor.getProvider().connection.getAccountInfo(programDataAddress);
+      // ProgramData layout: u32 tag, u64 slot, Option<Pubkey> authority
+      const authority = new anchor.web3.PublicKey(programData.data.subarray(13, 45));
+      if (!authority.equals(next)) {
+        throw new Error("Upgrade authority was not transferred!");
       }
+    }
+  });
 
-      // Resume for next test
+  it("Gives finalize proposals the extended timelock", async () => {
+    const finalizeProposalPda = await nextProposalPda();
+
+    await program.methods
+      .proposeAction({ finalizeProgram: {} }, program.programId, "Renounce upgrades", null)
+      .accounts({
+        proposal: finalizeProposalPda,
+        multisigConfig: multisigConfigPda,
+        proposer: anchor.getProvider().publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const proposalAccount = await program.account.upgradeProposal.fetch(finalizeProposalPda);
+    if (proposalAccount.timelockPeriod.toNumber() < 7 * 24 * 60 * 60) {
+      throw new Error("Finalize proposal did not get the extended timelock!");
+    }
+  });
+
+  it("Fast-tracks an emergency upgrade with every approver", async () => {
+    const emergencyBuffer = await createLockedBuffer();
+    const emergencyProposalPda = await nextProposalPda();
+
+    await program.methods
+      .proposeUpgrade(emergencyBuffer, program.programId, "Patch live exploit", null, true, [])
+      .accounts({
+        proposal: emergencyProposalPda,
+        multisigConfig: multisigConfigPda,
+        buffer: emergencyBuffer,
+        managedProgram: selfManagedProgramPda,
+        proposer: anchor.getProvider().publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const config = await program.account.multisigConfig.fetch(multisigConfigPda);
+    let proposalAccount = await program.account.upgradeProposal.fetch(emergencyProposalPda);
+    if (!proposalAccount.emergency) {
+      throw new Error("Proposal was not flagged as an emergency!");
+    }
+    if (!proposalAccount.timelockPeriod.eq(config.emergencyTimelock)) {
+      throw new Error("Emergency proposal did not get the emergency timelock!");
+    }
+
+    // The sole member is every approver, so one approval meets the supermajority
+    await program.methods
+      .approveUpgrade(emergencyProposalPda)
+      .accounts({
+        proposal: emergencyProposalPda,
+        multisigConfig: multisigConfigPda,
+        approver: anchor.getProvider().publicKey,
+      })
+      .rpc();
+
+    proposalAccount = await program.account.upgradeProposal.fetch(emergencyProposalPda);
+    if (!proposalAccount.status.timelockActive) {
+      throw new Error("Emergency timelock did not start!");
+    }
+  });
+
+  it("Executes governed instructions signed by the multisig", async () => {
+    // SPL Memo verifies that every account passed to it signed, so a memo listing the
+    // multisig PDA only succeeds if the PDA signed through invoke_signed
+    const MEMO_PROGRAM = new anchor.web3.PublicKey("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+    const instructionsProposalPda = await nextProposalPda();
+    const [proposalInstructionsPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("instructions"), instructionsProposalPda.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .proposeInstructions(
+        [
+          {
+            programId: MEMO_PROGRAM,
+            accounts: [{ pubkey: multisigConfigPda, isSigner: true, isWritable: false }],
+            data: Buffer.from("signed by governance"),
+          },
+        ],
+        "Governed memo",
+        null
+      )
+      .accounts({
+        multisigConfig: multisigConfigPda,
+        proposal: instructionsProposalPda,
+        proposalInstructions: proposalInstructionsPda,
+        proposer: anchor.getProvider().publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .approveUpgrade(instructionsProposalPda)
+      .accounts({
+        proposal: instructionsProposalPda,
+        multisigConfig: multisigConfigPda,
+        approver: anchor.getProvider().publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .executeInstructions()
+      .accounts({
+        proposal: instructionsProposalPda,
+        multisigConfig: multisigConfigPda,
+        proposalInstructions: proposalInstructionsPda,
+        executor: anchor.getProvider().publicKey,
+      })
+      .remainingAccounts([
+        { pubkey: MEMO_PROGRAM, isSigner: false, isWritable: false },
+      ])
+      .rpc();
+
+    const proposalAccount = await program.account.upgradeProposal.fetch(instructionsProposalPda);
+    if (!proposalAccount.status.executed) {
+      throw new Error("Governed instructions were not executed!");
+    }
+  });
+
+  it("Archives a resolved proposal once the retention period has passed", async () => {
+    const retentionProposalPda = await nextProposalPda();
+
+    await program.methods
+      .proposeAction(
+        { setRetentionPeriod: { retentionPeriod: new anchor.BN(0) } },
+        anchor.web3.PublicKey.default,
+        "No retention on localnet",
+        null
+      )
+      .accounts({
+        proposal: retentionProposalPda,
+        multisigConfig: multisigConfigPda,
+        proposer: anchor.getProvider().publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const { index } = await program.account.upgradeProposal.fetch(retentionProposalPda);
+    const [proposalSummaryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal_summary"), index.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    const closeAccounts = {
+      proposal: retentionProposalPda,
+      multisigConfig: multisigConfigPda,
+      proposalHistory: proposalHistoryPda,
+      proposalSummary: proposalSummaryPda,
+      proposalInstructions: null,
+      buffer: null,
+      bpfLoaderUpgradeable: null,
+      rentRecipient: anchor.getProvider().publicKey,
+      closer: anchor.getProvider().publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // Live proposals cannot be archived
+    await expectError(
+      program.methods.closeProposal().accounts(closeAccounts).rpc(),
+      "ProposalNotResolved"
+    );
+
+    await program.methods
+      .approveUpgrade(retentionProposalPda)
+      .accounts({
+        proposal: retentionProposalPda,
+        multisigConfig: multisigConfigPda,
+        approver: anchor.getProvider().publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .executeConfigChange()
+      .accounts({
+        proposal: retentionProposalPda,
+        multisigConfig: multisigConfigPda,
+        executor: anchor.getProvider().publicKey,
+      })
+      .rpc();
+
+    await program.methods.closeProposal().accounts(closeAccounts).rpc();
+
+    if (await anchor.getProvider().connection.getAccountInfo(retentionProposalPda)) {
+      throw new Error("Proposal was not closed!");
+    }
+    const summary = await program.account.proposalSummary.fetch(proposalSummaryPda);
+    if (!summary.proposalId.equals(retentionProposalPda) || !summary.status.executed) {
+      throw new Error("History summary mismatch!");
+    }
+  });
+
+  it("Refuses to start a migration before the upgrade is executed", async () => {
+    const [migrationTrackerPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("migration_tracker"), proposalPda.toBuffer()],
+      program.programId
+    );
+
+    await expectError(
+      program.methods
+        .startMigration(new anchor.BN(10))
+        .accounts({
+          proposal: proposalPda,
+          migrationTracker: migrationTrackerPda,
+          multisigConfig: multisigConfigPda,
+          migrator: anchor.getProvider().publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc(),
+      "InvalidProposalState"
+    );
+  });
+
+  it("Refuses to migrate an account outside a started migration", async () => {
+    // The history account is owned by a governed program (this one), but no
+    // migration has been started for the proposal
+    const [accountVersionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("migration"), proposalHistoryPda.toBuffer()],
+      program.programId
+    );
+    const [migrationTrackerPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("migration_tracker"), proposalPda.toBuffer()],
+      program.programId
+    );
+
+    await expectError(
+      program.methods
+        .migrateAccount(proposalHistoryPda, 2)
+        .accounts({
+          accountVersion: accountVersionPda,
+          multisigConfig: multisigConfigPda,
+          proposal: proposalPda,
+          migrationTracker: migrationTrackerPda,
+          oldAccount: proposalHistoryPda,
+          targetProgram: program.programId,
+          managedProgram: selfManagedProgramPda,
+          migrator: anchor.getProvider().publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc(),
+      "AccountNotInitialized"
+    );
+  });
+
+  it("Refuses to verify an account with no migration record", async () => {
+    const [accountVersionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("migration"), proposalHistoryPda.toBuffer()],
+      program.programId
+    );
+
+    await expectError(
+      program.methods
+        .verifyAccountVersion()
+        .accounts({
+          accountVersion: accountVersionPda,
+          account: proposalHistoryPda,
+        })
+        .rpc(),
+      "AccountNotInitialized"
+    );
+  });
+
+  // ==================== MIGRATION TESTS ====================
+
+  // Needs the fixture migration registered: anchor test -- --features test-fixtures
+  describe("Migration through a governed program's hook", () => {
+    const wallet = anchor.getProvider().publicKey;
+    const [fixtureManagedProgramPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("managed_program"), fixture.programId.toBuffer()],
+      program.programId
+    );
+    const fixtureProgramData = anchor.web3.PublicKey.findProgramAddressSync(
+      [fixture.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE
+    )[0];
+    const record = anchor.web3.Keypair.generate();
+    const [recordVersionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("migration"), record.publicKey.toBuffer()],
+      program.programId
+    );
+    let upgradeProposalPda: anchor.web3.PublicKey;
+    let migrationTrackerPda: anchor.web3.PublicKey;
+
+    async function approve(proposal: anchor.web3.PublicKey) {
+      await program.methods
+        .approveUpgrade(proposal)
+        .accounts({ proposal, multisigConfig: multisigConfigPda, approver: wallet })
+        .rpc();
+    }
+
+    function migrateRecord(
+      account: anchor.web3.PublicKey,
+      accountVersion: anchor.web3.PublicKey,
+      migrator: anchor.web3.PublicKey = wallet
+    ) {
+      return program.methods
+        .migrateAccount(account, 2)
+        .accounts({
+          accountVersion,
+          multisigConfig: multisigConfigPda,
+          proposal: upgradeProposalPda,
+          migrationTracker: migrationTrackerPda,
+          oldAccount: account,
+          targetProgram: fixture.programId,
+          managedProgram: fixtureManagedProgramPda,
+          migrator,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        });
+    }
+
+    it("Upgrades the fixture program through governance and starts a migration", async () => {
+      const provider = anchor.getProvider();
+
+      // 1. A version 1 record owned by the fixture program
+      await fixture.methods
+        .createRecord(new anchor.BN(42))
+        .accounts({
+          record: record.publicKey,
+          owner: wallet,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([record])
+        .rpc();
+
+      // 2. Place the fixture under governance and hand its upgrade authority to the multisig
+      const registerProposalPda = await nextProposalPda();
+      await program.methods
+        .proposeAction({ registerProgram: {} }, fixture.programId, "Register fixture", null)
+        .accounts({
+          proposal: registerProposalPda,
+          multisigConfig: multisigConfigPda,
+          proposer: wallet,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      await approve(registerProposalPda);
+      await program.methods
+        .registerProgram()
+        .accounts({
+          proposal: registerProposalPda,
+          multisigConfig: multisigConfigPda,
+          managedProgram: fixtureManagedProgramPda,
+          program: fixture.programId,
+          programData: fixtureProgramData,
+          executor: wallet,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      const transferProposalPda = await nextProposalPda();
+      await program.methods
+        .proposeAction(
+          { setUpgradeAuthorityChecked: { newAuthority: multisigConfigPda } },
+          fixture.programId,
+          "Govern fixture upgrades",
+          null
+        )
+        .accounts({
+          proposal: transferProposalPda,
+          multisigConfig: multisigConfigPda,
+          proposer: wallet,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      await approve(transferProposalPda);
+      await program.methods
+        .executeAuthorityTransfer()
+        .accounts({
+          proposal: transferProposalPda,
+          multisigConfig: multisigConfigPda,
+          managedProgram: fixtureManagedProgramPda,
+          programData: fixtureProgramData,
+          currentAuthority: wallet,
+          newAuthority: multisigConfigPda,
+          executor: wallet,
+          bpfLoaderUpgradeable: BPF_LOADER_UPGRADEABLE,
+        })
+        .rpc();
+
+      // 3. Upgrade the fixture (to the same binary) so a migration can start
+      const fixtureBuffer = await createLockedBuffer(
+        readFileSync("target/deploy/migration_hook_fixture.so")
+      );
+      upgradeProposalPda = await nextProposalPda();
+      await program.methods
+        .proposeUpgrade(fixtureBuffer, fixture.programId, "Record v2", null, false, [])
+        .accounts({
+          proposal: upgradeProposalPda,
+          multisigConfig: multisigConfigPda,
+          buffer: fixtureBuffer,
+          managedProgram: fixtureManagedProgramPda,
+          proposer: wallet,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      await approve(upgradeProposalPda);
+      await program.methods
+        .executeUpgrade(upgradeProposalPda)
+        .accounts({
+          proposal: upgradeProposalPda,
+          multisigConfig: multisigConfigPda,
+          managedProgram: fixtureManagedProgramPda,
+          programToUpgrade: fixture.programId,
+          programData: fixtureProgramData,
+          buffer: fixtureBuffer,
+          spillAccount: wallet,
+          executor: wallet,
+          bpfLoaderUpgradeable: BPF_LOADER_UPGRADEABLE,
+        })
+        .rpc();
+
+      // An upgraded program can only be invoked from the next slot
+      const upgradeSlot = await provider.connection.getSlot();
+      while ((await provider.connection.getSlot()) <= upgradeSlot) {
+        await new Promise((resolve) => setTimeout(resolve, 200));
+      }
+
+      // 4. Expect the wallet's record and one record migrated by its owner
+      [migrationTrackerPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("migration_tracker"), upgradeProposalPda.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .startMigration(new anchor.BN(2))
+        .accounts({
+          proposal: upgradeProposalPda,
+          migrationTracker: migrationTrackerPda,
+          multisigConfig: multisigConfigPda,
+          migrator: wallet,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    });
+
+    it("Refuses to migrate accounts of a program the proposal did not upgrade", async () => {
+      // This program is registered, and owns the history account, but was not upgraded
+      const [historyVersionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("migration"), proposalHistoryPda.toBuffer()],
+        program.programId
+      );
+
+      await expectError(
+        program.methods
+          .migrateAccount(proposalHistoryPda, 2)
+          .accounts({
+            accountVersion: historyVersionPda,
+            multisigConfig: multisigConfigPda,
+            proposal: upgradeProposalPda,
+            migrationTracker: migrationTrackerPda,
+            oldAccount: proposalHistoryPda,
+            targetProgram: program.programId,
+            managedProgram: selfManagedProgramPda,
+            migrator: wallet,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc(),
+        "InvalidTargetProgram"
+      );
+    });
+
+    it("Rejects a migration batch that is not made of account pairs", async () => {
+      await expectError(
+        program.methods
+          .migrateAccounts(2)
+          .accounts({
+            multisigConfig: multisigConfigPda,
+            proposal: upgradeProposalPda,
+            migrationTracker: migrationTrackerPda,
+            targetProgram: fixture.programId,
+            managedProgram: fixtureManagedProgramPda,
+            migrator: wallet,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .remainingAccounts([
+            { pubkey: record.publicKey, isWritable: true, isSigner: false },
+          ])
+          .rpc(),
+        "InvalidMigrationBatch"
+      );
+    });
+
+    it("Refuses to migrate a record the upgraded program created in the new layout", async () => {
+      // No version record exists, so the account counts as version 1; only its length
+      // shows it is already in the version 2 layout
+      const frozenRecord = anchor.web3.Keypair.generate();
+      const [frozenVersionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("migration"), frozenRecord.publicKey.toBuffer()],
+        program.programId
+      );
+      await fixture.methods
+        .createRecordV2(new anchor.BN(7), true)
+        .accounts({
+          record: frozenRecord.publicKey,
+          owner: wallet,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([frozenRecord])
+        .rpc();
+
+      await expectError(
+        migrateRecord(frozenRecord.publicKey, frozenVersionPda).rpc(),
+        "AccountLayoutMismatch"
+      );
+
+      const data = (await anchor.getProvider().connection.getAccountInfo(frozenRecord.publicKey)).data;
+      if (data[data.length - 1] !== 1) {
+        throw new Error("Frozen flag was reset!");
+      }
+    });
+
+    it("Migrates a record through the fixture's migration hook", async () => {
+      const connection = anchor.getProvider().connection;
+      const oldData = (await connection.getAccountInfo(record.publicKey)).data;
+
+      await migrateRecord(record.publicKey, recordVersionPda).rpc();
+
+      // Version 2 appends `frozen: false` to the version 1 layout
+      const newData = (await connection.getAccountInfo(record.publicKey)).data;
+      if (!newData.equals(Buffer.concat([oldData, Buffer.from([0])]))) {
+        throw new Error("Record was not rewritten into the version 2 layout!");
+      }
+
+      const sha256 = (data: Buffer) => createHash("sha256").update(data).digest();
+      const accountVersion = await program.account.accountVersion.fetch(recordVersionPda);
+      if (
+        accountVersion.version !== 2 ||
+        !accountVersion.migrated ||
+        !Buffer.from(accountVersion.oldDataHash).equals(sha256(oldData)) ||
+        !Buffer.from(accountVersion.newDataHash).equals(sha256(newData))
+      ) {
+        throw new Error("Account version record mismatch!");
+      }
+
+      await program.methods
+        .verifyAccountVersion()
+        .accounts({ accountVersion: recordVersionPda, account: record.publicKey })
+        .rpc();
+    });
+
+    it("Lets only a record's owner migrate it without ROLE_MIGRATOR", async () => {
+      const owner = await fundedKeypair();
+      const stranger = await fundedKeypair();
+      const ownedRecord = anchor.web3.Keypair.generate();
+      const [ownedVersionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("migration"), ownedRecord.publicKey.toBuffer()],
+        program.programId
+      );
+      await fixture.methods
+        .createRecord(new anchor.BN(5))
+        .accounts({
+          record: ownedRecord.publicKey,
+          owner: owner.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([ownedRecord, owner])
+        .rpc();
+
+      await expectError(
+        migrateRecord(ownedRecord.publicKey, ownedVersionPda, stranger.publicKey)
+          .signers([stranger])
+          .rpc(),
+        "UnauthorizedMigrator"
+      );
+
+      // Version 2 grows the record, so the owner path is open
+      await migrateRecord(ownedRecord.publicKey, ownedVersionPda, owner.publicKey)
+        .signers([owner])
+        .rpc();
+
+      const accountVersion = await program.account.accountVersion.fetch(ownedVersionPda);
+      if (accountVersion.version !== 2) {
+        throw new Error("Owner could not migrate their record!");
+      }
+    });
+
+    it("Finalizes the migration once every account is migrated", async () => {
+      await program.methods
+        .finalizeMigration()
+        .accounts({
+          migrationTracker: migrationTrackerPda,
+          multisigConfig: multisigConfigPda,
+          migrator: wallet,
+        })
+        .rpc();
+
+      const tracker = await program.account.migrationTracker.fetch(migrationTrackerPda);
+      if (tracker.migratedAccounts.toNumber() !== 2 || !tracker.completedAt) {
+        throw new Error("Migration was not completed!");
+      }
+    });
+  });
+
+  // ==================== EDGE CASE TESTS ====================
+
+  describe("Edge Cases", () => {
+    it("Prevents duplicate approval from the same member", async () => {
+      // Create a fresh proposal
+      const dupBuffer = await createLockedBuffer();
+      const dupProposalPda = await nextProposalPda();
+
+      await program.methods
+        .proposeUpgrade(dupBuffer, program.programId, "Test duplicate approval", null, false, [])
+        .accounts({
+          proposal: dupProposalPda,
+          multisigConfig: multisigConfigPda,
+          buffer: dupBuffer,
+          managedProgram: selfManagedProgramPda,
+          proposer: anchor.getProvider().publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      // First approval should succeed
+      await program.methods
+        .approveUpgrade(dupProposalPda)
+        .accounts({
+          proposal: dupProposalPda,
+          multisigConfig: multisigConfigPda,
+          approver: anchor.getProvider().publicKey,
+        })
+        .rpc();
+
+      // Second approval from same member should fail. The sole member meets the threshold,
+      // so the first approval already started the timelock and no further votes are taken.
+      await expectError(
+        program.methods
+          .approveUpgrade(dupProposalPda)
+          .accounts({
+            proposal: dupProposalPda,
+            multisigConfig: multisigConfigPda,
+            approver: anchor.getProvider().publicKey,
+          })
+          .rpc(),
+        "InvalidProposalState"
+      );
+    });
+
+    it("Revokes an approval and resets the timelock", async () => {
+      const revokeBuffer = await createLockedBuffer();
+      const revokeProposalPda = await nextProposalPda();
+
+      await program.methods
+        .proposeUpgrade(revokeBuffer, program.programId, "Test revocation", null, false, [])
+        .accounts({
+          proposal: revokeProposalPda,
+          multisigConfig: multisigConfigPda,
+          buffer: revokeBuffer,
+          managedProgram: selfManagedProgramPda,
+          proposer: anchor.getProvider().publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      await program.methods
+        .approveUpgrade(revokeProposalPda)
+        .accounts({
+          proposal: revokeProposalPda,
+          multisigConfig: multisigConfigPda,
+          approver: anchor.getProvider().publicKey,
+        })
+        .rpc();
+
+      await program.methods
+        .revokeApproval(revokeProposalPda)
+        .accounts({
+          proposal: revokeProposalPda,
+          multisigConfig: multisigConfigPda,
+          approver: anchor.getProvider().publicKey,
+        })
+        .rpc();
+
+      const proposal = await program.account.upgradeProposal.fetch(revokeProposalPda);
+      if (proposal.approvals.length !== 0 || !proposal.status.proposed) {
+        throw new Error("Approval was not revoked!");
+      }
+      if (proposal.timelockActivatedAt !== null) {
+        throw new Error("Timelock was not reset!");
+      }
+    });
+
+    it("Rejects a proposal once threshold is unreachable", async () => {
+      const rejectBuffer = await createLockedBuffer();
+      const rejectProposalPda = await nextProposalPda();
+
+      await program.methods
+        .proposeUpgrade(rejectBuffer, program.programId, "Test rejection", null, false, [])
+        .accounts({
+          proposal: rejectProposalPda,
+          multisigConfig: multisigConfigPda,
+          buffer: rejectBuffer,
+          managedProgram: selfManagedProgramPda,
+          proposer: anchor.getProvider().publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      // With a single member and threshold 1, one rejection makes approval impossible
+      await program.methods
+        .rejectUpgrade(rejectProposalPda)
+        .accounts({
+          proposal: rejectProposalPda,
+          multisigConfig: multisigConfigPda,
+          rejector: anchor.getProvider().publicKey,
+        })
+        .rpc();
+
+      const proposal = await program.account.upgradeProposal.fetch(rejectProposalPda);
+      if (!proposal.status.rejected) {
+        throw new Error("Proposal was not rejected!");
+      }
+      if (proposal.rejections.length !== 1) {
+        throw new Error("Rejection was not recorded!");
+      }
+
+      // Closing the rejected proposal (retention is 0 by now) refunds its locked buffer
+      const connection = anchor.getProvider().connection;
+      const wallet = anchor.getProvider().publicKey;
+      const [rejectSummaryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("proposal_summary"), proposal.index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const bufferLamports = (await connection.getAccountInfo(rejectBuffer)).lamports;
+      const proposalLamports = (await connection.getAccountInfo(rejectProposalPda)).lamports;
+      const balanceBefore = await connection.getBalance(wallet, "confirmed");
+
+      const signature = await program.methods
+        .closeProposal()
+        .accounts({
+          proposal: rejectProposalPda,
+          multisigConfig: multisigConfigPda,
+          proposalHistory: proposalHistoryPda,
+          proposalSummary: rejectSummaryPda,
+          proposalInstructions: null,
+          buffer: rejectBuffer,
+          bpfLoaderUpgradeable: BPF_LOADER_UPGRADEABLE,
+          rentRecipient: wallet,
+          closer: wallet,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc({ commitment: "confirmed" });
+
+      if (await connection.getAccountInfo(rejectBuffer)) {
+        throw new Error("Rejected proposal's buffer was not closed!");
+      }
+      // The wallet is proposer and closer, so it also pays the fee and the summary's rent
+      const { meta } = await connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const summaryLamports = (await connection.getAccountInfo(rejectSummaryPda)).lamports;
+      const balanceAfter = await connection.getBalance(wallet, "confirmed");
+      const expected = balanceBefore + bufferLamports + proposalLamports - summaryLamports - meta.fee;
+      if (balanceAfter !== expected) {
+        throw new Error(`Buffer rent was not refunded: expected ${expected}, got ${balanceAfter}`);
+      }
+    });
+
+    it("Prevents cancelling an already cancelled proposal", async () => {
+      // Create and cancel a proposal
+      const cancelBuffer2 = await createLockedBuffer();
+      const cancelProposalPda2 = await nextProposalPda();
+
+      await program.methods
+        .proposeUpgrade(cancelBuffer2, program.programId, "To be double-cancelled", null, false, [])
+        .accounts({
+          proposal: cancelProposalPda2,
+          multisigConfig: multisigConfigPda,
+          buffer: cancelBuffer2,
+          managedProgram: selfManagedProgramPda,
+          proposer: anchor.getProvider().publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      // First cancel
+      await program.methods
+        .cancelUpgrade(cancelProposalPda2, "Withdrawn", false)
+        .accounts({
+          proposal: cancelProposalPda2,
+          multisigConfig: multisigConfigPda,
+          canceller: anchor.getProvider().publicKey,
+          buffer: cancelBuffer2,
+          rentRecipient: anchor.getProvider().publicKey,
+          bpfLoaderUpgradeable: BPF_LOADER_UPGRADEABLE,
+          proposalHistory: null,
+          proposalSummary: null,
+          proposalInstructions: null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      // Second cancel should fail
+      await expectError(
+        program.methods
+          .cancelUpgrade(cancelProposalPda2, "Withdrawn", false)
+          .accounts({
+            proposal: cancelProposalPda2,
+            multisigConfig: multisigConfigPda,
+            canceller: anchor.getProvider().publicKey,
+            buffer: cancelBuffer2,
+            rentRecipient: anchor.getProvider().publicKey,
+            bpfLoaderUpgradeable: BPF_LOADER_UPGRADEABLE,
+            proposalHistory: null,
+            proposalSummary: null,
+            proposalInstructions: null,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc(),
+        "ProposalAlreadyCancelled"
+      );
+    });
+
+    it("Rejects adding an existing member", async () => {
+      const configProposalPda = await nextProposalPda();
+
+      await expectError(
+        program.methods
+          .proposeAction(
+            { addMember: { member: anchor.getProvider().publicKey, roles: 0b000010, weight: 1 } },
+            anchor.web3.PublicKey.default,
+            "Add existing member",
+            null
+          )
+          .accounts({
+            proposal: configProposalPda,
+            multisigConfig: multisigConfigPda,
+            proposer: anchor.getProvider().publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc(),
+        "DuplicateMember"
+      );
+    });
+
+    it("Rejects a threshold above the total approver weight", async () => {
+      const configProposalPda = await nextProposalPda();
+
+      await expectError(
+        program.methods
+          .proposeAction(
+            { changeThreshold: { threshold: 2 } },
+            anchor.web3.PublicKey.default,
+            "Unreachable threshold",
+            null
+          )
+          .accounts({
+            proposal: configProposalPda,
+            multisigConfig: multisigConfigPda,
+            proposer: anchor.getProvider().publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc(),
+        "InvalidThreshold"
+      );
+    });
+
+    it("Rejects a member with zero voting weight", async () => {
+      const configProposalPda = await nextProposalPda();
+
+      await expectError(
+        program.methods
+          .proposeAction(
+            { addMember: { member: anchor.web3.Keypair.generate().publicKey, roles: 0b000010, weight: 0 } },
+            anchor.web3.PublicKey.default,
+            "Weightless approver",
+            null
+          )
+          .accounts({
+            proposal: configProposalPda,
+            multisigConfig: multisigConfigPda,
+            proposer: anchor.getProvider().publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc(),
+        "InvalidWeight"
+      );
+    });
+
+    it("Rejects removing the approver role below the threshold", async () => {
+      const configProposalPda = await nextProposalPda();
+
+      await expectError(
+        program.methods
+          .proposeAction(
+            // Keep every role except approver
+            { setMemberRoles: { member: anchor.getProvider().publicKey, roles: 0b111101 } },
+            anchor.web3.PublicKey.default,
+            "Drop the only approver",
+            null
+          )
+          .accounts({
+            proposal: configProposalPda,
+            multisigConfig: multisigConfigPda,
+            proposer: anchor.getProvider().publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc(),
+        "InvalidThreshold"
+      );
+    });
+
+    it("Rejects a cancel quorum the cancellers cannot reach", async () => {
+      const configProposalPda = await nextProposalPda();
+
+      await expectError(
+        program.methods
+          .proposeAction(
+            { setCancelThresholds: { cancelThreshold: 1, activeCancelThreshold: 2 } },
+            anchor.web3.PublicKey.default,
+            "Unreachable cancel quorum",
+            null
+          )
+          .accounts({
+            proposal: configProposalPda,
+            multisigConfig: multisigConfigPda,
+            proposer: anchor.getProvider().publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc(),
+        "InvalidCancelThreshold"
+      );
+    });
+
+    it("Rejects a timelock shorter than the default", async () => {
+      const shortBuffer = await createLockedBuffer();
+      const shortProposalPda = await nextProposalPda();
+
+      await expectError(
+        program.methods
+          .proposeUpgrade(shortBuffer, program.programId, "Too short", new anchor.BN(-1), false, [])
+          .accounts({
+            proposal: shortProposalPda,
+            multisigConfig: multisigConfigPda,
+            buffer: shortBuffer,
+            managedProgram: selfManagedProgramPda,
+            proposer: anchor.getProvider().publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc(),
+        "InvalidTimelock"
+      );
+    });
+
+    it("Rejects a timelock longer than the maximum", async () => {
+      const longBuffer = await createLockedBuffer();
+      const longProposalPda = await nextProposalPda();
+
+      await expectError(
+        program.methods
+          .proposeUpgrade(longBuffer, program.programId, "Too long", new anchor.BN("9223372036854775807"), false, [])
+          .accounts({
+            proposal: longProposalPda,
+            multisigConfig: multisigConfigPda,
+            buffer: longBuffer,
+            managedProgram: selfManagedProgramPda,
+            proposer: anchor.getProvider().publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc(),
+        "InvalidTimelock"
+      );
+    });
+
+    it("Rejects a buffer that is not locked to the multisig", async () => {
+      const unlockedBuffer = anchor.web3.Keypair.generate().publicKey;
+      const unlockedProposalPda = await nextProposalPda();
+
+      await expectError(
+        program.methods
+          .proposeUpgrade(unlockedBuffer, program.programId, "Unlocked buffer", null, false, [])
+          .accounts({
+            proposal: unlockedProposalPda,
+            multisigConfig: multisigConfigPda,
+            buffer: unlockedBuffer,
+            managedProgram: selfManagedProgramPda,
+            proposer: anchor.getProvider().publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc(),
+        "InvalidProgramBuffer"
+      );
+    });
+
+    it("Proposes the same buffer twice under sequential indices", async () => {
+      const sharedBuffer = await createLockedBuffer();
+      const proposalPdas: anchor.web3.PublicKey[] = [];
+
+      for (const description of ["First attempt", "Second attempt"]) {
+        const pda = await nextProposalPda();
+        await program.methods
+          .proposeUpgrade(sharedBuffer, program.programId, description, null, false, [])
+          .accounts({
+            proposal: pda,
+            multisigConfig: multisigConfigPda,
+            buffer: sharedBuffer,
+            managedProgram: selfManagedProgramPda,
+            proposer: anchor.getProvider().publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc();
+        proposalPdas.push(pda);
+      }
+
+      const first = await program.account.upgradeProposal.fetch(proposalPdas[0]);
+      const second = await program.account.upgradeProposal.fetch(proposalPdas[1]);
+      if (!second.index.eq(first.index.addn(1))) {
+        throw new Error("Proposal indices are not sequential!");
+      }
+    });
+
+    it("Refuses to expire a live proposal", async () => {
+      const liveBuffer = await createLockedBuffer();
+      const liveProposalPda = await nextProposalPda();
+
+      await program.methods
+        .proposeUpgrade(liveBuffer, program.programId, "Still within deadline", null, false, [])
+        .accounts({
+          proposal: liveProposalPda,
+          multisigConfig: multisigConfigPda,
+          buffer: liveBuffer,
+          managedProgram: selfManagedProgramPda,
+          proposer: anchor.getProvider().publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      await expectError(
+        program.methods
+          .expireProposal()
+          .accounts({ proposal: liveProposalPda })
+          .rpc(),
+        "ProposalNotExpired"
+      );
+    });
+
+    it("Verifies proposal state after approval", async () => {
+      // Create and approve a proposal, then verify state
+      const stateBuffer = await createLockedBuffer();
+      const stateProposalPda = await nextProposalPda();
+
+      await program.methods
+        .proposeUpgrade(stateBuffer, program.programId, "State verification test", null, false, [])
+        .accounts({
+          proposal: stateProposalPda,
+          multisigConfig: multisigConfigPda,
+          buffer: stateBuffer,
+          managedProgram: selfManagedProgramPda,
+          proposer: anchor.getProvider().publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      // Check initial state
+      let proposal = await program.account.upgradeProposal.fetch(stateProposalPda);
+      console.log("Initial status:", proposal.status);
+      console.log("Initial approvals:", proposal.approvals);
+
+      // Approve
+      await program.methods
+        .approveUpgrade(stateProposalPda)
+        .accounts({
+          proposal: stateProposalPda,
+          multisigConfig: multisigConfigPda,
+          approver: anchor.getProvider().publicKey,
+        })
+        .rpc();
+
+      // Check state after approval
+      proposal = await program.account.upgradeProposal.fetch(stateProposalPda);
+      console.log("Status after approval:", proposal.status);
+      console.log("Approvals after:", proposal.approvals);
+
+      // Verify approval count increased
+      if (proposal.approvals.length === 0) {
+        throw new Error("Approval was not recorded!");
+      }
+      console.log("✓ Proposal state verified successfully");
+    });
+  });
+
+  // ==================== PAUSE/RESUME TESTS ====================
+
+  describe("Pause/Resume System", () => {
+    const PAUSE_PROPOSALS = 1 << 0;
+    const PAUSE_EXECUTION = 1 << 2;
+    const PAUSE_ALL = 0b1111;
+
+    it("Pauses the system", async () => {
+      const tx = await program.methods
+        .pauseSystem(PAUSE_ALL, "Incident drill")
+        .accounts({
+          multisigConfig: multisigConfigPda,
+          pauser: anchor.getProvider().publicKey,
+        })
+        .rpc();
+
+      console.log("Pause transaction signature", tx);
+
+      // Verify system is paused
+      const config = await program.account.multisigConfig.fetch(multisigConfigPda);
+      if (config.pausedScopes !== PAUSE_ALL) {
+        throw new Error("System was not paused!");
+      }
+      if (!config.pause.pausedBy.equals(anchor.getProvider().publicKey)) {
+        throw new Error("Pause record does not name the pauser!");
+      }
+      // A single member meeting the threshold ratifies their own pause
+      if (config.pause.expiresAt !== null) {
+        throw new Error("Pause should be ratified and not lapse!");
+      }
+      console.log("✓ System paused successfully");
+    });
+
+    it("Resumes the system", async () => {
+      const tx = await program.methods
+        .resumeSystem(PAUSE_ALL)
+        .accounts({
+          multisigConfig: multisigConfigPda,
+          resumer: anchor.getProvider().publicKey,
+        })
+        .rpc();
+
+      console.log("Resume transaction signature", tx);
+
+      // Verify system is resumed
+      const config = await program.account.multisigConfig.fetch(multisigConfigPda);
+      if (config.pausedScopes !== 0) {
+        throw new Error("System was not resumed!");
+      }
+      console.log("✓ System resumed successfully");
+    });
+
+    it("Blocks proposals only while that scope is paused", async () => {
+      await program.methods
+        .pauseSystem(PAUSE_PROPOSALS, "Freeze proposals")
+        .accounts({
+          multisigConfig: multisigConfigPda,
+          pauser: anchor.getProvider().publicKey,
+        })
+        .rpc();
+
+      const pausedBuffer = await createLockedBuffer();
+      const pausedProposalPda = await nextProposalPda();
+
+      await expectError(
+        program.methods
+          .proposeUpgrade(pausedBuffer, program.programId, "Should fail while paused", null, false, [])
+          .accounts({
+            proposal: pausedProposalPda,
+            multisigConfig: multisigConfigPda,
+            buffer: pausedBuffer,
+            managedProgram: selfManagedProgramPda,
+            proposer: anchor.getProvider().publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc(),
+        "SystemPaused"
+      );
+
+      // Resuming a scope that is not paused should fail
+      await expectError(
+        program.methods
+          .resumeSystem(PAUSE_EXECUTION)
+          .accounts({
+            multisigConfig: multisigConfigPda,
+            resumer: anchor.getProvider().publicKey,
+          })
+          .rpc(),
+        "SystemNotPaused"
+      );
+
+      await program.methods
+        .resumeSystem(PAUSE_PROPOSALS)
+        .accounts({
+          multisigConfig: multisigConfigPda,
+          resumer: anchor.getProvider().publicKey,
+        })
+        .rpc();
+    });
+
+    it("Prevents double pause", async () => {
+      // First pause
+      await program.methods
+        .pauseSystem(PAUSE_ALL, "Incident drill")
+        .accounts({
+          multisigConfig: multisigConfigPda,
+          pauser: anchor.getProvider().publicKey,
+        })
+        .rpc();
+
+      // Second pause should fail
+      await expectError(
+        program.methods
+          .pauseSystem(PAUSE_ALL, "Incident drill")
+          .accounts({
+            multisigConfig: multisigConfigPda,
+            pauser: anchor.getProvider().publicKey,
+          })
+          .rpc(),
+        "SystemAlreadyPaused"
+      );
+
+      // Resume for next test
       await program.methods
-        .resumeSystem()
+        .resumeSystem(PAUSE_ALL)
         .accounts({
           multisigConfig: multisigConfigPda,
           resumer: anchor.getProvider().publicKey,
@@ -429,4 +1841,344 @@ describe("program-upgrade-system", () => {
         .rpc();
     });
   });
+
+  // ==================== ROLE AND WEIGHT TESTS ====================
+
+  // Runs last: it adds members and raises the threshold above the wallet's own weight
+  describe("Members with limited roles and weights", () => {
+    const wallet = anchor.getProvider().publicKey;
+    const ROLE_PROPOSER = 1 << 0;
+    const ROLE_APPROVER = 1 << 1;
+    const ROLE_CANCELLER = 1 << 3;
+    const ROLE_PAUSER = 1 << 4;
+    const PAUSE_PROPOSALS = 1 << 0;
+    const PAUSE_APPROVALS = 1 << 1;
+    // Short enough to wait out, long enough to pause and ratify within
+    const PAUSE_WINDOW = 5;
+    // Can propose, but not vote, pause or cancel
+    let proposerOnly: anchor.web3.Keypair;
+    // Approves, pauses and cancels with weight 2; cannot propose
+    let heavyApprover: anchor.web3.Keypair;
+    // A config change that is harmless to approve
+    const noRetention = { setRetentionPeriod: { retentionPeriod: new anchor.BN(0) } };
+
+    async function approveAs(proposal: anchor.web3.PublicKey, approver?: anchor.web3.Keypair) {
+      await program.methods
+        .approveUpgrade(proposal)
+        .accounts({
+          proposal,
+          multisigConfig: multisigConfigPda,
+          approver: approver ? approver.publicKey : wallet,
+        })
+        .signers(approver ? [approver] : [])
+        .rpc();
+    }
+
+    function proposeActionAs(action: any, proposal: anchor.web3.PublicKey, proposer?: anchor.web3.Keypair) {
+      return program.methods
+        .proposeAction(action, anchor.web3.PublicKey.default, "Role test", null)
+        .accounts({
+          proposal,
+          multisigConfig: multisigConfigPda,
+          proposer: proposer ? proposer.publicKey : wallet,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers(proposer ? [proposer] : []);
+    }
+
+    // Proposes a config change as the wallet, approves it as `approver` and executes it
+    async function changeConfig(action: any, approver?: anchor.web3.Keypair) {
+      const proposal = await nextProposalPda();
+      await proposeActionAs(action, proposal).rpc();
+      await approveAs(proposal, approver);
+      await program.methods
+        .executeConfigChange()
+        .accounts({ proposal, multisigConfig: multisigConfigPda, executor: wallet })
+        .rpc();
+    }
+
+    function pauseAs(scopes: number, pauser?: anchor.web3.Keypair) {
+      return program.methods
+        .pauseSystem(scopes, "Pause drill")
+        .accounts({ multisigConfig: multisigConfigPda, pauser: pauser ? pauser.publicKey : wallet })
+        .signers(pauser ? [pauser] : [])
+        .rpc();
+    }
+
+    function ratifyAs(ratifier?: anchor.web3.Keypair) {
+      return program.methods
+        .ratifyPause()
+        .accounts({ multisigConfig: multisigConfigPda, ratifier: ratifier ? ratifier.publicKey : wallet })
+        .signers(ratifier ? [ratifier] : [])
+        .rpc();
+    }
+
+    function resumeAs(scopes: number, resumer?: anchor.web3.Keypair) {
+      return program.methods
+        .resumeSystem(scopes)
+        .accounts({ multisigConfig: multisigConfigPda, resumer: resumer ? resumer.publicKey : wallet })
+        .signers(resumer ? [resumer] : [])
+        .rpc();
+    }
+
+    // Waits until the cluster clock, which the pause window is measured against, reaches `timestamp`
+    async function waitForClock(timestamp: anchor.BN) {
+      const connection = anchor.getProvider().connection;
+      for (;;) {
+        const clock = await connection.getAccountInfo(anchor.web3.SYSVAR_CLOCK_PUBKEY);
+        // Clock layout: slot, epoch_start_timestamp, epoch, leader_schedule_epoch, unix_timestamp
+        if (clock && new anchor.BN(clock.data.subarray(32, 40), "le").gte(timestamp)) {
+          return;
+        }
+        await new Promise((resolve) => setTimeout(resolve, 500));
+      }
+    }
+
+    async function status(proposal: anchor.web3.PublicKey): Promise<string> {
+      const account = await program.account.upgradeProposal.fetch(proposal);
+      return Object.keys(account.status)[0];
+    }
+
+    it("Adds a proposer-only member and a weighted approver", async () => {
+      // Funded to pay for the proposals they create
+      proposerOnly = await fundedKeypair(anchor.web3.LAMPORTS_PER_SOL);
+      heavyApprover = await fundedKeypair(anchor.web3.LAMPORTS_PER_SOL);
+
+      await changeConfig(
+        { addMember: { member: proposerOnly.publicKey, roles: ROLE_PROPOSER, weight: 1 } }
+      );
+      await changeConfig(
+        {
+          addMember: {
+            member: heavyApprover.publicKey,
+            roles: ROLE_APPROVER | ROLE_CANCELLER | ROLE_PAUSER,
+            weight: 2,
+          },
+        }
+      );
+      // Approver weight is now 3: the wallet's 1 and the weighted approver's 2
+      await changeConfig({ changeThreshold: { threshold: 2 } });
+
+      const config = await program.account.multisigConfig.fetch(multisigConfigPda);
+      if (config.members.length !== 3 || config.threshold !== 2) {
+        throw new Error("Members or threshold were not updated!");
+      }
+    });
+
+    it("Refuses calls outside a member's roles", async () => {
+      const proposal = await nextProposalPda();
+      await proposeActionAs(noRetention, proposal, proposerOnly).rpc();
+
+      await expectError(
+        program.methods
+          .approveUpgrade(proposal)
+          .accounts({ proposal, multisigConfig: multisigConfigPda, approver: proposerOnly.publicKey })
+          .signers([proposerOnly])
+          .rpc(),
+        "MissingRole"
+      );
+      await expectError(
+        program.methods
+          .pauseSystem(PAUSE_PROPOSALS, "Not a pauser")
+          .accounts({ multisigConfig: multisigConfigPda, pauser: proposerOnly.publicKey })
+          .signers([proposerOnly])
+          .rpc(),
+        "MissingRole"
+      );
+      await expectError(
+        proposeActionAs(noRetention, await nextProposalPda(), heavyApprover).rpc(),
+        "MissingRole"
+      );
+    });
+
+    it("Counts approvals by weight", async () => {
+      // Weight 1 falls short of the threshold of 2, and the same member cannot vote twice
+      const lightProposal = await nextProposalPda();
+      await proposeActionAs(noRetention, lightProposal, proposerOnly).rpc();
+      await approveAs(lightProposal);
+      if ((await status(lightProposal)) !== "proposed") {
+        throw new Error("Weight 1 should not meet a threshold of 2!");
+      }
+      await expectError(approveAs(lightProposal), "DuplicateApproval");
+
+      // A single weight-2 approval crosses it
+      const heavyProposal = await nextProposalPda();
+      await proposeActionAs(noRetention, heavyProposal, proposerOnly).rpc();
+      await approveAs(heavyProposal, heavyApprover);
+      if ((await status(heavyProposal)) !== "timelockActive") {
+        throw new Error("Weight 2 should meet a threshold of 2!");
+      }
+    });
+
+    it("Requires every approver for an emergency upgrade", async () => {
+      const emergencyBuffer = await createLockedBuffer();
+      const emergencyProposalPda = await nextProposalPda();
+      await program.methods
+        .proposeUpgrade(emergencyBuffer, program.programId, "Emergency patch", null, true, [])
+        .accounts({
+          proposal: emergencyProposalPda,
+          multisigConfig: multisigConfigPda,
+          buffer: emergencyBuffer,
+          managedProgram: selfManagedProgramPda,
+          proposer: proposerOnly.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([proposerOnly])
+        .rpc();
+
+      // Weight 2 meets the regular threshold but not the supermajority of all 3
+      await approveAs(emergencyProposalPda, heavyApprover);
+      if ((await status(emergencyProposalPda)) !== "proposed") {
+        throw new Error("Emergency upgrade started its timelock without every approver!");
+      }
+      await approveAs(emergencyProposalPda);
+      if ((await status(emergencyProposalPda)) !== "timelockActive") {
+        throw new Error("Emergency upgrade did not start its timelock with every approver!");
+      }
+    });
+
+    it("Needs the active cancel quorum to cancel an approved proposal", async () => {
+      // Canceller weight is 3; a pending proposal still needs 1
+      await changeConfig(
+        { setCancelThresholds: { cancelThreshold: 1, activeCancelThreshold: 3 } },
+        heavyApprover
+      );
+
+      const cancelAs = (proposal: anchor.web3.PublicKey, canceller?: anchor.web3.Keypair) =>
+        program.methods
+          .cancelUpgrade(proposal, "Withdrawn", false)
+          .accounts({
+            proposal,
+            multisigConfig: multisigConfigPda,
+            canceller: canceller ? canceller.publicKey : wallet,
+            buffer: anchor.web3.Keypair.generate().publicKey, // Not an upgrade, so unused
+            rentRecipient: proposerOnly.publicKey,
+            bpfLoaderUpgradeable: BPF_LOADER_UPGRADEABLE,
+            proposalHistory: null,
+            proposalSummary: null,
+            proposalInstructions: null,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers(canceller ? [canceller] : [])
+          .rpc();
+
+      const pendingProposal = await nextProposalPda();
+      await proposeActionAs(noRetention, pendingProposal, proposerOnly).rpc();
+      await cancelAs(pendingProposal);
+      if ((await status(pendingProposal)) !== "cancelled") {
+        throw new Error("A single canceller should cancel a pending proposal!");
+      }
+
+      const approvedProposal = await nextProposalPda();
+      await proposeActionAs(noRetention, approvedProposal, proposerOnly).rpc();
+      await approveAs(approvedProposal, heavyApprover);
+      await cancelAs(approvedProposal);
+      if ((await status(approvedProposal)) !== "timelockActive") {
+        throw new Error("Weight 1 should not cancel an approved proposal!");
+      }
+      await cancelAs(approvedProposal, heavyApprover);
+      if ((await status(approvedProposal)) !== "cancelled") {
+        throw new Error("The active cancel quorum should cancel an approved proposal!");
+      }
+    });
+
+    it("Lets an unratified pause lapse after the pause window", async () => {
+      await changeConfig({ setPauseWindow: { pauseWindow: new anchor.BN(PAUSE_WINDOW) } }, heavyApprover);
+
+      // The wallet's weight of 1 falls short of the threshold, so its pause needs ratifying
+      await pauseAs(PAUSE_PROPOSALS);
+      const { pause } = await program.account.multisigConfig.fetch(multisigConfigPda);
+      if (pause.expiresAt === null) {
+        throw new Error("Pause below the threshold should lapse!");
+      }
+      await expectError(
+        proposeActionAs(noRetention, await nextProposalPda(), proposerOnly).rpc(),
+        "SystemPaused"
+      );
+
+      await waitForClock(pause.expiresAt);
+      await proposeActionAs(noRetention, await nextProposalPda(), proposerOnly).rpc();
+      await expectError(ratifyAs(heavyApprover), "SystemNotPaused");
+    });
+
+    it("Lets any pauser lift an unratified pause", async () => {
+      await pauseAs(PAUSE_PROPOSALS);
+      await resumeAs(PAUSE_PROPOSALS, heavyApprover);
+
+      const config = await program.account.multisigConfig.fetch(multisigConfigPda);
+      if (config.pausedScopes !== 0 || config.pause !== null) {
+        throw new Error("Unratified pause was not lifted!");
+      }
+    });
+
+    it("Needs threshold votes to lift a ratified pause", async () => {
+      await pauseAs(PAUSE_PROPOSALS);
+      await expectError(ratifyAs(), "DuplicateRatification");
+      await ratifyAs(heavyApprover);
+
+      const { pause } = await program.account.multisigConfig.fetch(multisigConfigPda);
+      if (pause.expiresAt !== null) {
+        throw new Error("Pause was not ratified!");
+      }
+      await expectError(ratifyAs(), "PauseAlreadyRatified");
+
+      // A ratified pause outlasts the window
+      await waitForClock(pause.pausedAt.addn(PAUSE_WINDOW));
+      await expectError(
+        proposeActionAs(noRetention, await nextProposalPda(), proposerOnly).rpc(),
+        "SystemPaused"
+      );
+
+      // Lifting it takes approvers: other members cannot, and weight 1 only casts a vote
+      await expectError(resumeAs(PAUSE_PROPOSALS, proposerOnly), "MissingRole");
+      await resumeAs(PAUSE_PROPOSALS);
+      const config = await program.account.multisigConfig.fetch(multisigConfigPda);
+      if (config.pausedScopes !== PAUSE_PROPOSALS || config.pause.resumeVotes.length !== 1) {
+        throw new Error("A vote below the threshold lifted the pause!");
+      }
+    });
+
+    it("Lets scopes added to a ratified pause lapse unless ratified", async () => {
+      // Added scopes await their own ratification; a pauser lifts them without votes
+      await pauseAs(PAUSE_APPROVALS);
+      let config = await program.account.multisigConfig.fetch(multisigConfigPda);
+      if (config.pause.pendingScopes !== PAUSE_APPROVALS || config.pause.pendingExpiresAt === null) {
+        throw new Error("Added scope was not left pending!");
+      }
+      await resumeAs(PAUSE_APPROVALS);
+      config = await program.account.multisigConfig.fetch(multisigConfigPda);
+      if (config.pausedScopes !== PAUSE_PROPOSALS || config.pause.pendingScopes !== 0) {
+        throw new Error("Pending scope was not lifted on its own!");
+      }
+
+      // Unratified, they lapse while the ratified scope stays paused
+      await pauseAs(PAUSE_APPROVALS);
+      config = await program.account.multisigConfig.fetch(multisigConfigPda);
+      await waitForClock(config.pause.pendingExpiresAt);
+      await expectError(ratifyAs(heavyApprover), "PauseAlreadyRatified");
+      await expectError(
+        proposeActionAs(noRetention, await nextProposalPda(), proposerOnly).rpc(),
+        "SystemPaused"
+      );
+
+      // Ratified, they join the pause and no longer lapse
+      await pauseAs(PAUSE_APPROVALS);
+      await ratifyAs(heavyApprover);
+      config = await program.account.multisigConfig.fetch(multisigConfigPda);
+      if (
+        config.pausedScopes !== (PAUSE_PROPOSALS | PAUSE_APPROVALS) ||
+        config.pause.pendingScopes !== 0 ||
+        config.pause.pendingExpiresAt !== null
+      ) {
+        throw new Error("Pending scope was not ratified!");
+      }
+
+      // The weighted approver's vote joins the wallet's and lifts every scope
+      await resumeAs(PAUSE_PROPOSALS, heavyApprover);
+      config = await program.account.multisigConfig.fetch(multisigConfigPda);
+      if (config.pausedScopes !== 0 || config.pause !== null) {
+        throw new Error("Threshold resume votes did not lift the pause!");
+      }
+    });
+  });
 });